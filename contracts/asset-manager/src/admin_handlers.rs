@@ -0,0 +1,260 @@
+use crate::{
+    error::Error,
+    get_fee_collector, get_force_withdraw_delay, get_operator_manager, get_owner,
+    get_withdraw_expiry,
+    storage_types::{
+        self, pair_manager::PairStorageInfo, timelock_manager::MAX_TIMELOCK_DELAY,
+        token_manager::DepositLimits, withdraw_limit_manager::WithdrawLimit, DataKey,
+        ListingStatus, OperatorRole,
+    },
+    types::{
+        AdminAction, OperatorRoleData, PairPriceDecimalsData, PairStatusData,
+        TokenDepositLimitsData, TokenStatusData, WithdrawLimitData,
+    },
+};
+use soroban_sdk::{assert_with_error, Address, BytesN, Env, String, Symbol};
+
+pub(crate) fn process_admin_action(e: &Env, action: AdminAction) {
+    match action {
+        AdminAction::SetTokenStatus(TokenStatusData { token, status }) => {
+            set_token_status(e, token, status);
+        }
+        AdminAction::SetTokenDepositLimits(TokenDepositLimitsData { token, limits }) => {
+            set_token_deposit_limits(e, token, limits);
+        }
+        AdminAction::SetPairStatus(PairStatusData {
+            symbol,
+            token1,
+            token2,
+            status,
+        }) => set_pair_status(e, symbol, token1, token2, status),
+        AdminAction::SetPairPriceDecimals(PairPriceDecimalsData {
+            symbol,
+            price_decimals,
+        }) => set_pair_price_decimals(e, symbol, price_decimals),
+        AdminAction::SetOperatorManager(operator_manager) => {
+            set_operator_manager(e, operator_manager);
+        }
+        AdminAction::SetFeeCollector(fee_collector) => set_fee_collector(e, fee_collector),
+        AdminAction::GrantOperatorRole(OperatorRoleData { operator, role }) => {
+            grant_operator_role(e, operator, role);
+        }
+        AdminAction::RevokeOperatorRole(OperatorRoleData { operator, role }) => {
+            revoke_operator_role(e, operator, role);
+        }
+        AdminAction::SetGuardian(guardian) => set_guardian(e, guardian),
+        AdminAction::SetTimelockDelay(delay) => set_timelock_delay(e, delay),
+        AdminAction::SetForceWithdrawDelay(delay) => set_force_withdraw_delay(e, delay),
+        AdminAction::SetWithdrawExpiry(expiry) => set_withdraw_expiry(e, expiry),
+        AdminAction::SetSelfTradePrevention(enabled) => set_self_trade_prevention(e, enabled),
+        AdminAction::SetWithdrawLimit(WithdrawLimitData { token, limit }) => {
+            set_withdraw_limit(e, token, limit);
+        }
+        AdminAction::RemoveWithdrawLimit(token) => remove_withdraw_limit(e, token),
+        AdminAction::Upgrade(new_wasm_hash) => upgrade(e, new_wasm_hash),
+    }
+}
+
+pub(crate) fn upgrade(e: &Env, new_wasm_hash: BytesN<32>) {
+    e.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    let topics = (Symbol::new(e, "upgrade"), get_owner(e));
+    e.events().publish(topics, new_wasm_hash);
+}
+
+pub(crate) fn set_operator_manager(e: &Env, operator_manager: Address) {
+    let previous_operator_manager = get_operator_manager(e);
+
+    e.storage()
+        .instance()
+        .set(&DataKey::OperatorManager, &operator_manager);
+
+    emit_role_address_update(
+        e,
+        "operator_manager_updated",
+        &previous_operator_manager,
+        operator_manager,
+    );
+}
+
+/// Fees accrued before the change stay on the previous collector's balances,
+/// so it can still withdraw them the same way as any other user.
+pub(crate) fn set_fee_collector(e: &Env, fee_collector: Address) {
+    let previous_fee_collector = get_fee_collector(e);
+
+    e.storage()
+        .instance()
+        .set(&DataKey::FeeCollector, &fee_collector);
+
+    emit_role_address_update(
+        e,
+        "fee_collector_updated",
+        &previous_fee_collector,
+        fee_collector,
+    );
+}
+
+pub(crate) fn grant_operator_role(e: &Env, operator: Address, role: OperatorRole) {
+    let role_manager = storage_types::OperatorRoleManager::new(operator, role);
+    role_manager.grant_role(e);
+
+    role_manager.emit_role_update(e, true);
+}
+
+pub(crate) fn revoke_operator_role(e: &Env, operator: Address, role: OperatorRole) {
+    let role_manager = storage_types::OperatorRoleManager::new(operator, role);
+    role_manager.revoke_role(e);
+
+    role_manager.emit_role_update(e, false);
+}
+
+pub(crate) fn set_guardian(e: &Env, guardian: Address) {
+    e.storage().instance().set(&DataKey::Guardian, &guardian);
+
+    let topics = (Symbol::new(e, "guardian_updated"), get_owner(e));
+    e.events().publish(topics, guardian);
+}
+
+pub(crate) fn set_timelock_delay(e: &Env, delay: u32) {
+    assert_with_error!(
+        e,
+        delay <= MAX_TIMELOCK_DELAY,
+        Error::ErrInvalidTimelockDelay
+    );
+
+    e.storage().instance().set(&DataKey::TimelockDelay, &delay);
+
+    let topics = (Symbol::new(e, "timelock_delay"), get_owner(e));
+    e.events().publish(topics, delay);
+}
+
+/// Pending requests have to stay force withdrawable for a while before they can expire,
+/// otherwise a sweep could take the escape path away from the user.
+pub(crate) fn set_force_withdraw_delay(e: &Env, delay: u32) {
+    assert_with_error!(
+        e,
+        delay < get_withdraw_expiry(e),
+        Error::ErrInvalidWithdrawExpiry
+    );
+
+    e.storage()
+        .instance()
+        .set(&DataKey::ForceWithdrawDelay, &delay);
+
+    let topics = (Symbol::new(e, "force_withdraw_delay"), get_owner(e));
+    e.events().publish(topics, delay);
+}
+
+pub(crate) fn set_withdraw_expiry(e: &Env, expiry: u32) {
+    assert_with_error!(
+        e,
+        expiry > get_force_withdraw_delay(e),
+        Error::ErrInvalidWithdrawExpiry
+    );
+
+    e.storage()
+        .instance()
+        .set(&DataKey::WithdrawExpiry, &expiry);
+
+    let topics = (Symbol::new(e, "withdraw_expiry"), get_owner(e));
+    e.events().publish(topics, expiry);
+}
+
+pub(crate) fn set_self_trade_prevention(e: &Env, enabled: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::SelfTradePolicy, &enabled);
+
+    let topics = (Symbol::new(e, "self_trade_prevention"), get_owner(e));
+    e.events().publish(topics, enabled);
+}
+
+pub(crate) fn set_withdraw_limit(e: &Env, token: Address, limit: WithdrawLimit) {
+    assert_with_error!(
+        e,
+        limit.window > 0 && limit.user_cap > 0 && limit.global_cap > 0,
+        Error::ErrInvalidWithdrawLimit
+    );
+
+    let withdraw_limit_manager = storage_types::WithdrawLimitManager::new(token);
+
+    withdraw_limit_manager.set_limit(e, &limit);
+
+    withdraw_limit_manager.emit_limit_update(e, Some(limit));
+}
+
+pub(crate) fn remove_withdraw_limit(e: &Env, token: Address) {
+    let withdraw_limit_manager = storage_types::WithdrawLimitManager::new(token);
+
+    withdraw_limit_manager.remove_limit(e);
+
+    withdraw_limit_manager.emit_limit_update(e, None);
+}
+
+pub(crate) fn set_token_status(e: &Env, token: Address, status: ListingStatus) {
+    let token_manager = storage_types::TokenManager::new(token);
+
+    token_manager.set_listing_status(e, &status);
+
+    token_manager.emit_listing_status(e, status);
+}
+
+pub(crate) fn set_token_deposit_limits(e: &Env, token: Address, limits: DepositLimits) {
+    assert_with_error!(
+        e,
+        limits.min_deposit >= 0
+            && limits.max_user_balance > 0
+            && limits.deposit_cap > 0
+            && limits.min_deposit <= limits.max_user_balance,
+        Error::ErrInvalidDepositLimits
+    );
+
+    let token_manager = storage_types::TokenManager::new(token);
+
+    token_manager.set_deposit_limits(e, &limits);
+
+    token_manager.emit_deposit_limits(e, limits);
+}
+
+pub(crate) fn set_pair_status(
+    e: &Env,
+    symbol: String,
+    token1: Address,
+    token2: Address,
+    status: ListingStatus,
+) {
+    assert_pair_token_status(e, &token1, &status);
+    assert_pair_token_status(e, &token2, &status);
+
+    let pair_manager = storage_types::PairManager::new(symbol);
+
+    let pair_info = PairStorageInfo::new((token1, token2), status.clone());
+    pair_manager.set_pair_info(e, &pair_info);
+
+    pair_manager.emit_listing_status(e, pair_info.get_pair(), status);
+}
+
+pub(crate) fn set_pair_price_decimals(e: &Env, symbol: String, price_decimals: u32) {
+    let pair_manager = storage_types::PairManager::new(symbol);
+
+    pair_manager.set_price_decimals(e, price_decimals);
+    pair_manager.emit_price_decimals(e, price_decimals);
+}
+
+/// A pair can only be opened for trading while its tokens are,
+/// winding it down only requires the tokens to have been listed.
+fn assert_pair_token_status(e: &Env, token: &Address, pair_status: &ListingStatus) {
+    let token_status = storage_types::TokenManager::new(token.clone()).read_status(e);
+
+    assert_with_error!(
+        e,
+        token_status.is_some_and(|status| !pair_status.allows_trading() || status.allows_trading()),
+        Error::ErrTokenIsNotListed
+    );
+}
+
+fn emit_role_address_update(e: &Env, event: &str, previous: &Address, new: Address) {
+    let topics = (Symbol::new(e, event), previous);
+    e.events().publish(topics, new);
+}
@@ -22,4 +22,51 @@ pub enum Error {
     ErrWithdrawRequestDataMismatch = 15,
     ErrTradeSymbolsNotMatch = 16,
     ErrBatchIdNotMatch = 17,
+    // Ownership related errors
+    ErrNoPendingOwner = 18,
+    // Operator role related errors
+    ErrOperatorNotAuthorized = 19,
+    // Pause related errors
+    ErrOperationPaused = 20,
+    ErrNotAuthorized = 21,
+    // Upgrade related errors
+    ErrAlreadyMigrated = 22,
+    // Timelock related errors
+    ErrTimelockRequired = 23,
+    ErrAdminActionNotQueued = 24,
+    ErrTimelockNotExpired = 25,
+    ErrInvalidTimelockDelay = 26,
+    // Force withdraw related errors
+    ErrForceWithdrawNotAvailable = 27,
+    // Withdraw limit related errors
+    ErrInvalidWithdrawLimit = 28,
+    // Withdraw expiry related errors
+    ErrInvalidWithdrawExpiry = 29,
+    // Deposit related errors
+    ErrInvalidDepositLimits = 30,
+    ErrDepositBelowMinimum = 31,
+    ErrUserBalanceCapExceeded = 32,
+    ErrDepositCapExceeded = 33,
+    // Listing status related errors
+    ErrDepositNotAllowed = 34,
+    ErrTradingNotAllowed = 35,
+    // Trade related errors
+    ErrPairIsNotListed = 36,
+    ErrTradeTokenIsNotListed = 37,
+    // Order related errors
+    ErrInvalidOrder = 38,
+    ErrOrderTradeMismatch = 39,
+    ErrOrderExpired = 40,
+    ErrOrderFeeLimitExceeded = 41,
+    ErrTradeAlreadySettled = 42,
+    ErrOrderOverfilled = 43,
+    // Price related errors
+    ErrTradeSideMismatch = 44,
+    ErrTradeAmountMismatch = 45,
+    ErrPriceLimitExceeded = 46,
+    ErrInvalidPriceDecimals = 47,
+    // Trade legs related errors
+    ErrTradeLegsMismatch = 48,
+    ErrSelfTrade = 49,
+    ErrDuplicateTradeId = 50,
 }
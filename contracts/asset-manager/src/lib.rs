@@ -1,16 +1,26 @@
 #![no_std]
 use crate::{
     error::Error,
-    storage_types::{pair_manager::PairStorageInfo, DataKey, WithdrawData, WithdrawStatus},
+    storage_types::{
+        timelock_manager::QueuedAdminAction, DataKey, WithdrawData, WithdrawStatus,
+        INSTANCE_BUMP_AMOUNT, MAX_PAGE_LIMIT,
+    },
 };
-use operator_handlers::{process_trades_batch, process_withdraw_request};
+use operator_handlers::{process_trades_batch, process_withdraw_batch, process_withdraw_request};
 use soroban_sdk::{
     assert_with_error, contract, contractimpl, panic_with_error, token, Address, BytesN, Env,
-    String,
+    String, Symbol, Vec,
 };
-use storage_types::{user_balance_manager::UserBalances, ListingStatus};
-use types::{OperatorAction, ValidateUserSignatureData};
+use storage_types::{
+    order_fill_manager::OrderFill,
+    token_manager::{DepositLimits, TokenConfig},
+    user_balance_manager::UserBalances,
+    withdraw_limit_manager::WithdrawLimit,
+    ListingStatus, OperatorRole, PausableOperation,
+};
+use types::{AdminAction, OperatorAction, ValidateUserSignatureData};
 
+mod admin_handlers;
 mod error;
 mod operator_handlers;
 mod storage_types;
@@ -20,6 +30,13 @@ mod test;
 mod test_utils;
 mod types;
 
+/// Bumped together with the storage migrations performed by `migrate`.
+#[cfg(not(feature = "next-version"))]
+pub(crate) const CONTRACT_VERSION: u32 = 1;
+/// Only used to build a second WASM for the upgrade test to move to.
+#[cfg(feature = "next-version")]
+pub(crate) const CONTRACT_VERSION: u32 = 2;
+
 fn get_owner(e: &Env) -> Address {
     if let Some(operator_manager) = e.storage().instance().get::<_, Address>(&DataKey::Owner) {
         operator_manager
@@ -28,6 +45,23 @@ fn get_owner(e: &Env) -> Address {
     }
 }
 
+fn get_pending_owner(e: &Env) -> Address {
+    if let Some(pending_owner) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::PendingOwner)
+    {
+        pending_owner
+    } else {
+        panic_with_error!(&e, Error::ErrNoPendingOwner)
+    }
+}
+
+fn emit_owner_transfer(e: &Env, event: &str, owner: &Address, pending_owner: Address) {
+    let topics = (Symbol::new(e, event), owner);
+    e.events().publish(topics, pending_owner);
+}
+
 fn get_operator_manager(e: &Env) -> Address {
     if let Some(operator_manager) = e
         .storage()
@@ -40,6 +74,16 @@ fn get_operator_manager(e: &Env) -> Address {
     }
 }
 
+fn is_operator_authorized(e: &Env, operator: &Address, role: OperatorRole) -> bool {
+    // the operator manager keeps every role, dedicated keys are limited to granted ones
+    *operator == get_operator_manager(e)
+        || storage_types::OperatorRoleManager::new(operator.clone(), role).has_role(e)
+}
+
+fn get_guardian(e: &Env) -> Option<Address> {
+    e.storage().instance().get::<_, Address>(&DataKey::Guardian)
+}
+
 fn get_fee_collector(e: &Env) -> Address {
     if let Some(fee_collector) = e
         .storage()
@@ -52,6 +96,114 @@ fn get_fee_collector(e: &Env) -> Address {
     }
 }
 
+fn get_version(e: &Env) -> u32 {
+    // contracts deployed before the version tracking have no stored version
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::Version)
+        .unwrap_or(0)
+}
+
+fn get_timelock_delay(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::TimelockDelay)
+        .unwrap_or(0)
+}
+
+/// Authorizes an owner change applied right away, which is only possible
+/// until a timelock delay is configured. Afterwards the change has to be
+/// queued with `queue_admin_action`.
+fn authorize_owner_action(e: &Env) {
+    let owner = get_owner(e);
+    owner.require_auth();
+
+    assert_with_error!(e, get_timelock_delay(e) == 0, Error::ErrTimelockRequired);
+}
+
+fn get_force_withdraw_delay(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::ForceWithdrawDelay)
+        .unwrap_or(storage_types::DEFAULT_FORCE_WITHDRAW_DELAY)
+}
+
+fn get_withdraw_expiry(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::WithdrawExpiry)
+        .unwrap_or(storage_types::DEFAULT_WITHDRAW_EXPIRY)
+}
+
+fn get_self_trade_prevention(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<_, bool>(&DataKey::SelfTradePolicy)
+        .unwrap_or(true)
+}
+
+/// Moves the amount of a request that will not be executed back to the user balance.
+fn unlock_withdraw_amount(e: &Env, withdraw_request: &WithdrawData) {
+    let user_balance_manager = storage_types::UserBalanceManager::new(
+        withdraw_request.user.clone(),
+        withdraw_request.token.clone(),
+    );
+    user_balance_manager.modify_user_balance_with(e, |balances| {
+        let mut balances = balances;
+        assert_with_error!(
+            e,
+            balances.balance_on_withdraw >= withdraw_request.amount,
+            Error::ErrBalanceNotEnough
+        );
+        balances.balance_on_withdraw -= withdraw_request.amount;
+        balances.balance += withdraw_request.amount;
+        balances
+    });
+}
+
+fn process_deposit(
+    e: &Env,
+    from: &Address,
+    beneficiary: Address,
+    token: &Address,
+    amount: i128,
+) -> storage_types::UserBalanceManager {
+    assert_with_error!(e, amount > 0, Error::ErrAmountMustBePositive);
+    storage_types::PauseManager::new(PausableOperation::Deposit).assert_not_paused(e);
+
+    let user_balance_manager = storage_types::UserBalanceManager::new(beneficiary, token.clone());
+    let mut balances = user_balance_manager.read_user_balance(e);
+
+    let token_manager = storage_types::TokenManager::new(token.clone());
+    token_manager.assert_deposit_allowed(
+        e,
+        balances.balance + balances.balance_on_withdraw,
+        amount,
+    );
+
+    let client = token::Client::new(e, token);
+    client.transfer(from, &e.current_contract_address(), &amount);
+
+    balances.balance += amount;
+    user_balance_manager.write_user_balance(e, &balances);
+    token_manager.add_deposited(e, amount);
+
+    user_balance_manager
+}
+
+fn bump_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .bump(INSTANCE_BUMP_AMOUNT, INSTANCE_BUMP_AMOUNT);
+}
+
+fn get_new_admin_action_id(e: &Env) -> u64 {
+    let key = DataKey::AdminActionId;
+    let id = e.storage().instance().get::<_, u64>(&key).unwrap_or(1);
+    e.storage().instance().set(&key, &(id + 1));
+    id
+}
+
 fn get_new_withdraw_id(e: &Env) -> u64 {
     let key = DataKey::WithdrawId;
     let id = e.storage().instance().get::<_, u64>(&key).unwrap();
@@ -80,6 +232,8 @@ struct AssetManager;
 #[allow(clippy::needless_pass_by_value)]
 impl AssetManager {
     pub fn initialize(e: Env, owner: Address, operator_manager: Address, fee_collector: Address) {
+        bump_instance(&e);
+
         assert_with_error!(
             &e,
             !e.storage().instance().has(&DataKey::Owner),
@@ -99,41 +253,358 @@ impl AssetManager {
         e.storage()
             .instance()
             .set::<DataKey, u64>(&DataKey::BatchId, &1);
+        e.storage()
+            .instance()
+            .set::<DataKey, u32>(&DataKey::Version, &CONTRACT_VERSION);
+    }
+
+    pub fn version(e: Env) -> u32 {
+        bump_instance(&e);
+
+        get_version(&e)
+    }
+
+    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::upgrade(&e, new_wasm_hash);
+    }
+
+    /// Runs once after an upgrade which bumped `CONTRACT_VERSION`.
+    pub fn migrate(e: Env) {
+        bump_instance(&e);
+
+        let owner = get_owner(&e);
+        owner.require_auth();
+
+        let stored_version = get_version(&e);
+        assert_with_error!(
+            &e,
+            stored_version < CONTRACT_VERSION,
+            Error::ErrAlreadyMigrated
+        );
+
+        // storage migrations between `stored_version` and `CONTRACT_VERSION` go here,
+        // listings and withdraw requests written before version 1 are converted lazily on read
+        if stored_version == 0 {
+            e.storage()
+                .instance()
+                .set::<DataKey, u32>(&DataKey::MigrationLedger, &e.ledger().sequence());
+        }
+
+        e.storage()
+            .instance()
+            .set::<DataKey, u32>(&DataKey::Version, &CONTRACT_VERSION);
+
+        let topics = (Symbol::new(&e, "migrate"), &owner);
+        e.events()
+            .publish(topics, (stored_version, CONTRACT_VERSION));
+    }
+
+    pub fn timelock_delay(e: Env) -> u32 {
+        bump_instance(&e);
+
+        get_timelock_delay(&e)
+    }
+
+    pub fn set_timelock_delay(e: Env, delay: u32) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_timelock_delay(&e, delay);
+    }
+
+    pub fn queued_admin_action(e: Env, id: u64) -> Option<QueuedAdminAction> {
+        bump_instance(&e);
+
+        storage_types::TimelockManager::new(id).read_queued_action(&e)
+    }
+
+    /// Queues the action to be executed once `timelock_delay` ledgers have passed.
+    pub fn queue_admin_action(e: Env, action: AdminAction) -> u64 {
+        bump_instance(&e);
+
+        let owner = get_owner(&e);
+        owner.require_auth();
+
+        let id = get_new_admin_action_id(&e);
+        let timelock_manager = storage_types::TimelockManager::new(id);
+
+        let queued_action = QueuedAdminAction {
+            action,
+            eta: e.ledger().sequence().saturating_add(get_timelock_delay(&e)),
+        };
+        timelock_manager.write_queued_action(&e, &queued_action);
+
+        timelock_manager.emit_admin_action_queued(&e, queued_action);
+
+        id
+    }
+
+    pub fn cancel_admin_action(e: Env, id: u64) {
+        bump_instance(&e);
+
+        let owner = get_owner(&e);
+        owner.require_auth();
+
+        let timelock_manager = storage_types::TimelockManager::new(id);
+        let queued_action = timelock_manager.get_queued_action(&e);
+        timelock_manager.remove_queued_action(&e);
+
+        timelock_manager.emit_admin_action_status(&e, "admin_action_cancelled", queued_action);
+    }
+
+    pub fn execute_admin_action(e: Env, id: u64) {
+        bump_instance(&e);
+
+        let owner = get_owner(&e);
+        owner.require_auth();
+
+        let timelock_manager = storage_types::TimelockManager::new(id);
+        let queued_action = timelock_manager.get_queued_action(&e);
+
+        assert_with_error!(
+            &e,
+            e.ledger().sequence() >= queued_action.eta,
+            Error::ErrTimelockNotExpired
+        );
+
+        timelock_manager.remove_queued_action(&e);
+        admin_handlers::process_admin_action(&e, queued_action.action.clone());
+
+        timelock_manager.emit_admin_action_status(&e, "admin_action_executed", queued_action);
     }
 
     pub fn owner(e: Env) -> Address {
+        bump_instance(&e);
+
         get_owner(&e)
     }
 
+    pub fn pending_owner(e: Env) -> Option<Address> {
+        bump_instance(&e);
+
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PendingOwner)
+    }
+
+    pub fn propose_owner(e: Env, new_owner: Address) {
+        bump_instance(&e);
+
+        let owner = get_owner(&e);
+        owner.require_auth();
+
+        e.storage()
+            .instance()
+            .set(&DataKey::PendingOwner, &new_owner);
+
+        emit_owner_transfer(&e, "owner_transfer_proposed", &owner, new_owner);
+    }
+
+    pub fn accept_owner(e: Env) {
+        bump_instance(&e);
+
+        let pending_owner = get_pending_owner(&e);
+        pending_owner.require_auth();
+
+        let previous_owner = get_owner(&e);
+
+        e.storage().instance().set(&DataKey::Owner, &pending_owner);
+        e.storage().instance().remove(&DataKey::PendingOwner);
+
+        emit_owner_transfer(
+            &e,
+            "owner_transfer_accepted",
+            &previous_owner,
+            pending_owner,
+        );
+    }
+
+    pub fn cancel_owner_transfer(e: Env) {
+        bump_instance(&e);
+
+        let owner = get_owner(&e);
+        owner.require_auth();
+
+        let pending_owner = get_pending_owner(&e);
+        e.storage().instance().remove(&DataKey::PendingOwner);
+
+        emit_owner_transfer(&e, "owner_transfer_cancelled", &owner, pending_owner);
+    }
+
     pub fn operator_manager(e: Env) -> Address {
+        bump_instance(&e);
+
         get_operator_manager(&e)
     }
 
     pub fn fee_collector(e: Env) -> Address {
+        bump_instance(&e);
+
         get_fee_collector(&e)
     }
 
+    pub fn set_operator_manager(e: Env, operator_manager: Address) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_operator_manager(&e, operator_manager);
+    }
+
+    pub fn set_fee_collector(e: Env, fee_collector: Address) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_fee_collector(&e, fee_collector);
+    }
+
+    pub fn has_operator_role(e: Env, operator: Address, role: OperatorRole) -> bool {
+        bump_instance(&e);
+
+        storage_types::OperatorRoleManager::new(operator, role).has_role(&e)
+    }
+
+    pub fn grant_operator_role(e: Env, operator: Address, role: OperatorRole) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::grant_operator_role(&e, operator, role);
+    }
+
+    pub fn revoke_operator_role(e: Env, operator: Address, role: OperatorRole) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::revoke_operator_role(&e, operator, role);
+    }
+
+    pub fn guardian(e: Env) -> Option<Address> {
+        bump_instance(&e);
+
+        get_guardian(&e)
+    }
+
+    pub fn set_guardian(e: Env, guardian: Address) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_guardian(&e, guardian);
+    }
+
+    pub fn is_paused(e: Env, operation: PausableOperation) -> bool {
+        bump_instance(&e);
+
+        storage_types::PauseManager::new(operation).is_paused(&e)
+    }
+
+    /// The guardian is only able to pause, resuming an operation is left to the owner.
+    pub fn set_pause_status(e: Env, caller: Address, operation: PausableOperation, paused: bool) {
+        bump_instance(&e);
+
+        caller.require_auth();
+
+        let is_owner = caller == get_owner(&e);
+        let is_guardian = get_guardian(&e) == Some(caller.clone());
+        assert_with_error!(
+            &e,
+            is_owner || (is_guardian && paused),
+            Error::ErrNotAuthorized
+        );
+
+        let pause_manager = storage_types::PauseManager::new(operation);
+        pause_manager.set_paused(&e, paused);
+
+        pause_manager.emit_pause_status(&e, paused);
+    }
+
     pub fn is_token_listed(e: Env, token: Address) -> bool {
+        bump_instance(&e);
+
         let token_manager = storage_types::TokenManager::new(token);
 
         token_manager.is_listed(&e)
     }
 
+    pub fn token_config(e: Env, token: Address) -> Option<TokenConfig> {
+        bump_instance(&e);
+
+        storage_types::TokenManager::new(token).read_config(&e)
+    }
+
+    /// Every token ever listed with its current status, delisted ones included.
+    pub fn list_tokens(e: Env) -> Vec<(Address, ListingStatus)> {
+        bump_instance(&e);
+
+        let mut tokens = Vec::new(&e);
+        for token in storage_types::TokenManager::read_registry(&e) {
+            if let Some(status) = storage_types::TokenManager::new(token.clone()).read_status(&e) {
+                tokens.push_back((token, status));
+            }
+        }
+
+        tokens
+    }
+
+    /// Lists pairs in the order they were first listed, at most `MAX_PAGE_LIMIT` per call.
+    pub fn list_pairs(
+        e: Env,
+        start: u32,
+        limit: u32,
+    ) -> Vec<(String, Address, Address, ListingStatus)> {
+        bump_instance(&e);
+
+        let registry = storage_types::PairManager::read_registry(&e);
+        let end = registry
+            .len()
+            .min(start.saturating_add(limit.min(MAX_PAGE_LIMIT)));
+
+        let mut pairs = Vec::new(&e);
+        for index in start..end {
+            let symbol = registry.get_unchecked(index);
+            if let Some(pair_info) =
+                storage_types::PairManager::new(symbol.clone()).read_pair_info(&e)
+            {
+                let status = pair_info.status();
+                let (token1, token2) = pair_info.get_pair();
+                pairs.push_back((symbol, token1, token2, status));
+            }
+        }
+
+        pairs
+    }
+
     pub fn is_pair_listed(e: Env, symbol: String) -> bool {
+        bump_instance(&e);
+
         let pair_manager = storage_types::PairManager::new(symbol);
 
         pair_manager.is_listed(&e)
     }
 
     pub fn set_token_status(e: Env, token: Address, status: ListingStatus) {
-        let owner = get_owner(&e);
-        owner.require_auth();
+        bump_instance(&e);
 
-        let token_manager = storage_types::TokenManager::new(token);
+        authorize_owner_action(&e);
+
+        admin_handlers::set_token_status(&e, token, status);
+    }
+
+    /// Limits can only be set on a token which has been listed.
+    pub fn set_token_deposit_limits(e: Env, token: Address, limits: DepositLimits) {
+        bump_instance(&e);
 
-        token_manager.set_listing_status(&e, &status);
+        authorize_owner_action(&e);
 
-        token_manager.emit_listing_status(&e, status);
+        admin_handlers::set_token_deposit_limits(&e, token, limits);
     }
 
     pub fn set_pair_status(
@@ -143,59 +614,83 @@ impl AssetManager {
         token2: Address,
         status: ListingStatus,
     ) {
-        let owner = get_owner(&e);
-        owner.require_auth();
+        bump_instance(&e);
 
-        assert_with_error!(
-            &e,
-            storage_types::TokenManager::new(token1.clone()).is_listed(&e),
-            Error::ErrTokenIsNotListed
-        );
+        authorize_owner_action(&e);
 
-        assert_with_error!(
-            &e,
-            storage_types::TokenManager::new(token2.clone()).is_listed(&e),
-            Error::ErrTokenIsNotListed
-        );
+        admin_handlers::set_pair_status(&e, symbol, token1, token2, status);
+    }
 
-        let pair_manager = storage_types::PairManager::new(symbol);
+    pub fn pair_price_decimals(e: Env, symbol: String) -> u32 {
+        bump_instance(&e);
+
+        storage_types::PairManager::new(symbol).read_price_decimals(&e)
+    }
 
-        let pair_info = PairStorageInfo::new((token1, token2), status.clone());
-        pair_manager.set_pair_info(&e, &pair_info);
+    pub fn set_pair_price_decimals(e: Env, symbol: String, price_decimals: u32) {
+        bump_instance(&e);
 
-        pair_manager.emit_listing_status(&e, pair_info.get_pair(), status);
+        authorize_owner_action(&e);
+
+        admin_handlers::set_pair_price_decimals(&e, symbol, price_decimals);
+    }
+
+    /// Whether trades matching two orders of the same account are rejected, on by default.
+    pub fn self_trade_prevention(e: Env) -> bool {
+        bump_instance(&e);
+
+        get_self_trade_prevention(&e)
+    }
+
+    pub fn set_self_trade_prevention(e: Env, enabled: bool) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_self_trade_prevention(&e, enabled);
     }
 
     pub fn balances(e: Env, user: Address, token: Address) -> UserBalances {
+        bump_instance(&e);
+
         storage_types::UserBalanceManager::new(user, token).read_user_balance(&e)
     }
 
     pub fn deposit(e: Env, user: Address, token: Address, amount: i128) {
+        bump_instance(&e);
+
         user.require_auth();
-        assert_with_error!(&e, amount > 0, Error::ErrAmountMustBePositive);
 
-        let token_whitelisted = storage_types::TokenManager::new(token.clone());
-        assert_with_error!(
-            &e,
-            token_whitelisted.is_listed(&e),
-            Error::ErrTokenIsNotListed
-        );
+        let user_balance_manager = process_deposit(&e, &user, user.clone(), &token, amount);
 
-        let client = token::Client::new(&e, &token);
-        client.transfer(&user, &e.current_contract_address(), &amount);
+        user_balance_manager.emit_deposit(&e, amount);
+    }
 
-        let user_balance_manager =
-            storage_types::UserBalanceManager::new(user.clone(), token.clone());
-        let mut balances = user_balance_manager.read_user_balance(&e);
-        balances.balance += amount;
-        user_balance_manager.write_user_balance(&e, &balances);
+    /// Pulls the tokens from `from` and credits them to the `beneficiary` balance.
+    pub fn deposit_for(e: Env, from: Address, beneficiary: Address, token: Address, amount: i128) {
+        bump_instance(&e);
 
-        user_balance_manager.emit_deposit(&e, amount);
+        from.require_auth();
+
+        let user_balance_manager = process_deposit(&e, &from, beneficiary, &token, amount);
+
+        user_balance_manager.emit_deposit_for(&e, &from, amount);
     }
 
-    pub fn request_withdraw(e: Env, user: Address, token: Address, amount: i128) -> u64 {
+    /// Tokens are sent to `to` once the request is executed, or to the user when it is omitted.
+    pub fn request_withdraw(
+        e: Env,
+        user: Address,
+        token: Address,
+        amount: i128,
+        to: Option<Address>,
+    ) -> u64 {
+        bump_instance(&e);
+
         user.require_auth();
         assert_with_error!(&e, amount > 0, Error::ErrAmountMustBePositive);
+        storage_types::PauseManager::new(PausableOperation::RequestWithdraw).assert_not_paused(&e);
+        storage_types::TokenManager::new(token.clone()).assert_withdraw_allowed(&e);
 
         let user_balance_manager =
             storage_types::UserBalanceManager::new(user.clone(), token.clone());
@@ -213,18 +708,234 @@ impl AssetManager {
             token,
             amount,
             status: WithdrawStatus::Requested,
+            to: to.unwrap_or_else(|| user.clone()),
             user,
+            created_ledger: e.ledger().sequence(),
         };
 
         user_balance_manager.write_user_balance(&e, &balances);
         withdraw_manager.write_withdraw_request(&e, &withdraw_request_data);
+        storage_types::UserWithdrawIndexManager::new(withdraw_request_data.user.clone())
+            .push_withdraw_id(&e, new_id);
 
         withdraw_manager.emit_withdraw_request(&e, withdraw_request_data);
 
         new_id
     }
 
+    pub fn get_withdraw_request(e: Env, id: u64) -> WithdrawData {
+        bump_instance(&e);
+
+        storage_types::WithdrawRequestManager::new(id).read_withdraw_request(&e)
+    }
+
+    pub fn user_withdraw_request_count(e: Env, user: Address) -> u32 {
+        bump_instance(&e);
+
+        storage_types::UserWithdrawIndexManager::new(user).read_count(&e)
+    }
+
+    /// Lists the user's withdraw requests oldest first, together with their ids.
+    /// At most `MAX_PAGE_LIMIT` requests are returned per call.
+    pub fn user_withdraw_requests(
+        e: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<(u64, WithdrawData)> {
+        bump_instance(&e);
+
+        let ids = storage_types::UserWithdrawIndexManager::new(user).read_withdraw_ids(
+            &e,
+            start,
+            limit.min(MAX_PAGE_LIMIT),
+        );
+
+        let mut requests = Vec::new(&e);
+        for id in ids {
+            let withdraw_request =
+                storage_types::WithdrawRequestManager::new(id).read_withdraw_request(&e);
+            requests.push_back((id, withdraw_request));
+        }
+
+        requests
+    }
+
+    pub fn force_withdraw_delay(e: Env) -> u32 {
+        bump_instance(&e);
+
+        get_force_withdraw_delay(&e)
+    }
+
+    pub fn set_force_withdraw_delay(e: Env, delay: u32) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_force_withdraw_delay(&e, delay);
+    }
+
+    pub fn withdraw_limit(e: Env, token: Address) -> Option<WithdrawLimit> {
+        bump_instance(&e);
+
+        storage_types::WithdrawLimitManager::new(token).read_limit(&e)
+    }
+
+    pub fn set_withdraw_limit(e: Env, token: Address, limit: WithdrawLimit) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_withdraw_limit(&e, token, limit);
+    }
+
+    pub fn remove_withdraw_limit(e: Env, token: Address) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::remove_withdraw_limit(&e, token);
+    }
+
+    /// Lets the user receive the tokens of a request the operator did not process
+    /// within `force_withdraw_delay` ledgers. Not affected by pausing, so users can always exit.
+    pub fn force_withdraw(e: Env, user: Address, id: u64) {
+        bump_instance(&e);
+
+        user.require_auth();
+
+        let withdraw_manager = storage_types::WithdrawRequestManager::new(id);
+        let mut withdraw_request = withdraw_manager.read_user_pending_request(&e, &user);
+
+        assert_with_error!(
+            &e,
+            e.ledger().sequence()
+                >= withdraw_request
+                    .created_ledger
+                    .saturating_add(get_force_withdraw_delay(&e)),
+            Error::ErrForceWithdrawNotAvailable
+        );
+
+        let user_balance_manager =
+            storage_types::UserBalanceManager::new(user.clone(), withdraw_request.token.clone());
+        user_balance_manager.modify_user_balance_with(&e, |balances| {
+            let mut balances = balances;
+            assert_with_error!(
+                &e,
+                balances.balance_on_withdraw >= withdraw_request.amount,
+                Error::ErrBalanceNotEnough
+            );
+            balances.balance_on_withdraw -= withdraw_request.amount;
+            balances
+        });
+
+        let client = token::Client::new(&e, &withdraw_request.token);
+        client.transfer(
+            &e.current_contract_address(),
+            &withdraw_request.to,
+            &withdraw_request.amount,
+        );
+        storage_types::TokenManager::new(withdraw_request.token.clone())
+            .remove_deposited(&e, withdraw_request.amount);
+
+        withdraw_request.status = WithdrawStatus::ForceWithdrawn;
+        withdraw_manager.write_withdraw_request(&e, &withdraw_request);
+
+        withdraw_manager.emit_withdraw_request(&e, withdraw_request);
+    }
+
+    pub fn withdraw_expiry(e: Env) -> u32 {
+        bump_instance(&e);
+
+        get_withdraw_expiry(&e)
+    }
+
+    pub fn set_withdraw_expiry(e: Env, expiry: u32) {
+        bump_instance(&e);
+
+        authorize_owner_action(&e);
+
+        admin_handlers::set_withdraw_expiry(&e, expiry);
+    }
+
+    /// Callable by anyone. Pending requests older than `withdraw_expiry` ledgers are marked
+    /// `Expired` and their amount returns to the user balance, other ids, unknown ones included, are skipped.
+    /// Returns the number of requests expired.
+    pub fn sweep_expired_withdraws(e: Env, ids: Vec<u64>) -> u32 {
+        bump_instance(&e);
+
+        let expiry = get_withdraw_expiry(&e);
+        let mut expired: u32 = 0;
+
+        for id in ids {
+            let withdraw_manager = storage_types::WithdrawRequestManager::new(id);
+            let Some(mut withdraw_request) = withdraw_manager.get_withdraw_request(&e) else {
+                continue;
+            };
+
+            if !withdraw_request.status.is_pending()
+                || e.ledger().sequence() < withdraw_request.created_ledger.saturating_add(expiry)
+            {
+                continue;
+            }
+
+            unlock_withdraw_amount(&e, &withdraw_request);
+
+            withdraw_request.status = WithdrawStatus::Expired;
+            withdraw_manager.write_withdraw_request(&e, &withdraw_request);
+
+            withdraw_manager.emit_withdraw_request(&e, withdraw_request);
+            expired += 1;
+        }
+
+        expired
+    }
+
+    pub fn cancel_withdraw(e: Env, user: Address, id: u64) {
+        bump_instance(&e);
+
+        user.require_auth();
+
+        let withdraw_manager = storage_types::WithdrawRequestManager::new(id);
+        let mut withdraw_request = withdraw_manager.read_user_pending_request(&e, &user);
+
+        unlock_withdraw_amount(&e, &withdraw_request);
+
+        withdraw_request.status = WithdrawStatus::Cancelled;
+        withdraw_manager.write_withdraw_request(&e, &withdraw_request);
+
+        withdraw_manager.emit_withdraw_request(&e, withdraw_request);
+    }
+
+    /// Callable by anyone, e.g. a keeper, so the data of idle users doesn't get archived.
+    /// Entries which don't exist are skipped, only the latest withdraw requests are extended.
+    pub fn extend_ttl(e: Env, user: Address, tokens: Vec<Address>, key_ids: Vec<u32>) {
+        bump_instance(&e);
+
+        for token in tokens {
+            storage_types::UserBalanceManager::new(user.clone(), token).bump(&e);
+        }
+
+        for key_id in key_ids {
+            storage_types::KeyManager::new(user.clone(), key_id).bump(&e);
+        }
+
+        storage_types::UserWithdrawIndexManager::new(user.clone()).bump_recent(&e, MAX_PAGE_LIMIT);
+
+        storage_types::UserTtlManager::new(user).write_extended_ledger(&e);
+    }
+
+    /// Ledger until which the last `extend_ttl` call keeps the user entries alive, 0 if there
+    /// was none. Only covers keeper extensions, entries used since then live longer.
+    pub fn user_data_extended_until(e: Env, user: Address) -> u32 {
+        bump_instance(&e);
+
+        storage_types::UserTtlManager::new(user).read_extended_until(&e)
+    }
+
     pub fn user_announce_key(e: Env, user: Address, key_id: u32, public_key: BytesN<32>) {
+        bump_instance(&e);
+
         user.require_auth();
 
         let user_key_manager = storage_types::KeyManager::new(user, key_id);
@@ -234,13 +945,30 @@ impl AssetManager {
     }
 
     pub fn get_user_key(e: Env, user: Address, key_id: u32) -> BytesN<32> {
+        bump_instance(&e);
+
         let user_key_manager = storage_types::KeyManager::new(user, key_id);
         user_key_manager.read_public_key(&e)
     }
 
-    pub fn execute_action(e: Env, action: OperatorAction) {
-        let operator_manager = get_operator_manager(&e);
-        operator_manager.require_auth();
+    /// Fills settled so far for the order, keyed by the sha256 of its signed XDR.
+    /// Orders which never got settled have no fills.
+    pub fn order_fill_status(e: Env, order_hash: BytesN<32>) -> Option<OrderFill> {
+        bump_instance(&e);
+
+        storage_types::OrderFillManager::new(order_hash).read_order_fill(&e)
+    }
+
+    pub fn execute_action(e: Env, operator: Address, action: OperatorAction) {
+        bump_instance(&e);
+
+        operator.require_auth();
+
+        assert_with_error!(
+            &e,
+            is_operator_authorized(&e, &operator, action.required_role()),
+            Error::ErrOperatorNotAuthorized
+        );
 
         match action {
             OperatorAction::ValidateUserSignature(data) => {
@@ -255,10 +983,19 @@ impl AssetManager {
                 e.crypto().ed25519_verify(&public_key, &message, &signature);
             }
             OperatorAction::ExecuteWithdraw(execution_withdraw_data) => {
+                storage_types::PauseManager::new(PausableOperation::ExecuteWithdraw)
+                    .assert_not_paused(&e);
                 process_withdraw_request(&e, execution_withdraw_data);
             }
+            OperatorAction::ExecuteWithdrawBatch(withdraw_batch) => {
+                storage_types::PauseManager::new(PausableOperation::ExecuteWithdraw)
+                    .assert_not_paused(&e);
+                process_withdraw_batch(&e, withdraw_batch);
+            }
             OperatorAction::TradeUpload(trade_unit_data) => {
-                process_trades_batch(&e, trade_unit_data)
+                storage_types::PauseManager::new(PausableOperation::TradeUpload)
+                    .assert_not_paused(&e);
+                process_trades_batch(&e, trade_unit_data);
             }
         }
     }
@@ -1,12 +1,19 @@
 use crate::{
     error::Error,
     get_batch_id, increment_batch_id,
-    storage_types::{self, UserBalanceManager, WithdrawStatus},
+    storage_types::{self, TokenManager, UserBalanceManager, WithdrawLimitManager, WithdrawStatus},
     types::{trade_upload::TradeUploadData, ExecutionWithdrawData, OperatorWithdrawStatus},
 };
-use soroban_sdk::{assert_with_error, token, Env, Symbol};
-
-pub(crate) fn process_withdraw_request(e: &Env, withdraw_data: ExecutionWithdrawData) {
+use soroban_sdk::{assert_with_error, token, Env, Symbol, Vec};
+
+/// Approved requests exceeding the token withdraw limit are queued instead of executed,
+/// the operator approves them again once the limit window has passed.
+/// A request above one of the caps stays queued until the owner raises the cap
+/// or the operator rejects it.
+pub(crate) fn process_withdraw_request(
+    e: &Env,
+    withdraw_data: ExecutionWithdrawData,
+) -> WithdrawStatus {
     let ExecutionWithdrawData {
         id,
         user,
@@ -21,7 +28,7 @@ pub(crate) fn process_withdraw_request(e: &Env, withdraw_data: ExecutionWithdraw
 
     assert_with_error!(
         &e,
-        withdraw_request.status == WithdrawStatus::Requested,
+        withdraw_request.status.is_pending(),
         Error::ErrWithdrawRequestAlreadyProcessed
     );
 
@@ -33,7 +40,19 @@ pub(crate) fn process_withdraw_request(e: &Env, withdraw_data: ExecutionWithdraw
         Error::ErrWithdrawRequestDataMismatch
     );
 
-    let user_balance_manager = UserBalanceManager::new(user.clone(), token.clone());
+    if let OperatorWithdrawStatus::Approve = execution_status {
+        if !WithdrawLimitManager::new(token.clone()).try_consume(e, &user, amount) {
+            // a request queued before stays as it is until the limit allows it
+            if withdraw_request.status != WithdrawStatus::Queued {
+                withdraw_request.status = WithdrawStatus::Queued;
+                withdraw_request_manager.write_withdraw_request(e, &withdraw_request);
+                withdraw_request_manager.emit_withdraw_request(e, withdraw_request);
+            }
+            return WithdrawStatus::Queued;
+        }
+    }
+
+    let user_balance_manager = UserBalanceManager::new(user, token.clone());
     let mut balances = user_balance_manager.read_user_balance(e);
 
     match execution_status {
@@ -49,7 +68,8 @@ pub(crate) fn process_withdraw_request(e: &Env, withdraw_data: ExecutionWithdraw
             withdraw_request.status = WithdrawStatus::Executed;
 
             let client = token::Client::new(e, &token);
-            client.transfer(&e.current_contract_address(), &user, &amount);
+            client.transfer(&e.current_contract_address(), &withdraw_request.to, &amount);
+            TokenManager::new(token.clone()).remove_deposited(e, amount);
         }
         OperatorWithdrawStatus::Reject => {
             withdraw_request.status = WithdrawStatus::Rejected;
@@ -62,9 +82,29 @@ pub(crate) fn process_withdraw_request(e: &Env, withdraw_data: ExecutionWithdraw
 
     user_balance_manager.write_user_balance(e, &balances);
 
+    let status = withdraw_request.status.clone();
+
     withdraw_request_manager.write_withdraw_request(e, &withdraw_request);
 
     withdraw_request_manager.emit_withdraw_request(e, withdraw_request);
+
+    status
+}
+
+pub(crate) fn process_withdraw_batch(e: &Env, withdraw_batch: Vec<ExecutionWithdrawData>) {
+    let mut approved: u32 = 0;
+    let mut rejected: u32 = 0;
+    let mut queued: u32 = 0;
+
+    for withdraw_data in withdraw_batch {
+        match process_withdraw_request(e, withdraw_data) {
+            WithdrawStatus::Queued => queued += 1,
+            WithdrawStatus::Rejected => rejected += 1,
+            _ => approved += 1,
+        }
+    }
+
+    emit_withdraw_batch_processed(e, approved, rejected, queued);
 }
 
 pub(crate) fn process_trades_batch(e: &Env, trade_data: TradeUploadData) {
@@ -75,6 +115,7 @@ pub(crate) fn process_trades_batch(e: &Env, trade_data: TradeUploadData) {
     );
 
     for trade_pair in trade_data.trades {
+        trade_pair.assert_legs_match(e);
         trade_pair.verify_signatures(e);
 
         trade_pair.execute_pair_swap(e);
@@ -84,6 +125,11 @@ pub(crate) fn process_trades_batch(e: &Env, trade_data: TradeUploadData) {
     emit_trades_batch_processed(e, trade_data.batch_id);
 }
 
+fn emit_withdraw_batch_processed(e: &Env, approved: u32, rejected: u32, queued: u32) {
+    let topics = (Symbol::new(e, "withdraw_batch_processed"),);
+    e.events().publish(topics, (approved, rejected, queued));
+}
+
 fn emit_trades_batch_processed(e: &Env, batch_id: u64) {
     let topics = (Symbol::new(e, "batch_processed"),);
     e.events().publish(topics, batch_id);
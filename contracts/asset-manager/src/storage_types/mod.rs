@@ -1,29 +1,84 @@
+pub(crate) mod operator_role_manager;
+pub(crate) mod order_fill_manager;
 pub(crate) mod pair_manager;
+pub(crate) mod pause_manager;
 pub(crate) mod public_key_manager;
+pub(crate) mod settled_trade_manager;
+pub(crate) mod timelock_manager;
 pub(crate) mod token_manager;
 pub(crate) mod user_balance_manager;
+pub(crate) mod user_ttl_manager;
+pub(crate) mod user_withdraw_index_manager;
+pub(crate) mod withdraw_limit_manager;
 pub(crate) mod withdraw_request_manager;
 
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, String, TryFromVal, Val};
 
 // pub(crate) const SHARED_BUMP_AMOUNT: u32 = 69120; // 4 days
-pub(crate) const USER_DATA_BUMP_AMOUNT: u32 = 518400; // 30 days
+pub(crate) const USER_DATA_BUMP_AMOUNT: u32 = 518_400; // 30 days
+pub(crate) const LISTING_BUMP_AMOUNT: u32 = 518_400; // 30 days
+pub(crate) const INSTANCE_BUMP_AMOUNT: u32 = 518_400; // 30 days
+pub(crate) const DEFAULT_FORCE_WITHDRAW_DELAY: u32 = 120_960; // 7 days
+pub(crate) const DEFAULT_WITHDRAW_EXPIRY: u32 = 518_400; // 30 days
+pub(crate) const MAX_PAGE_LIMIT: u32 = 50;
 
+/// Withdrawing is allowed in every state, so winding down an asset never locks balances in.
 #[contracttype]
 #[derive(PartialEq, Clone)]
 pub enum ListingStatus {
-    Listed,
-    Delisted,
+    Listed,         // Deposits, withdrawing and trading
+    Delisted,       // Withdrawing only, users take out what is left of their balance
+    DepositsPaused, // Withdrawing and trading only
+    TradingHalted,  // Deposits and withdrawing only
+}
+
+impl ListingStatus {
+    pub fn allows_deposit(&self) -> bool {
+        matches!(self, ListingStatus::Listed | ListingStatus::TradingHalted)
+    }
+
+    pub fn allows_trading(&self) -> bool {
+        matches!(self, ListingStatus::Listed | ListingStatus::DepositsPaused)
+    }
+}
+
+#[contracttype]
+#[derive(PartialEq, Clone, Copy)]
+pub enum OperatorRole {
+    WithdrawExecutor,
+    TradeUploader,
+    SignatureValidator,
+}
+
+#[contracttype]
+#[derive(PartialEq, Clone, Copy)]
+pub enum PausableOperation {
+    All, // Pauses every operation below at once
+    Deposit,
+    RequestWithdraw,
+    ExecuteWithdraw,
+    TradeUpload,
 }
 
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
-    Owner,           // Address of the account Owner
-    OperatorManager, // Address of the Operator Manager
-    FeeCollector,    // Address of the Fee Collector
-    WithdrawId,      // u64 for the new id
-    BatchId,         // u64 for the batch counting
+    Owner,              // Address of the account Owner
+    PendingOwner,       // Address proposed as the next Owner
+    OperatorManager,    // Address of the Operator Manager
+    FeeCollector,       // Address of the Fee Collector
+    Guardian,           // Address allowed to pause operations besides the Owner
+    WithdrawId,         // u64 for the new id
+    BatchId,            // u64 for the batch counting
+    Version,            // u32 version of the storage layout the contract was migrated to
+    TimelockDelay,      // u32 amount of ledgers a queued admin action waits before execution
+    AdminActionId,      // u64 for the new queued admin action id
+    ForceWithdrawDelay, // u32 amount of ledgers after which a user can force its withdraw request
+    WithdrawExpiry,     // u32 amount of ledgers after which a pending withdraw request can expire
+    TokenRegistry,      // Vec<Address> of every token ever listed
+    PairRegistry,       // Vec<String> of every pair symbol ever listed
+    SelfTradePolicy,    // bool whether trades between two orders of one account are rejected
+    MigrationLedger,    // u32 ledger the storage was migrated to version 1 at
 }
 
 #[derive(Clone)]
@@ -45,12 +100,53 @@ pub struct PairManager {
     pub symbol: String,
 }
 
+#[contracttype]
+pub struct OperatorRoleManager {
+    pub operator: Address,
+    pub role: OperatorRole,
+}
+
+#[contracttype]
+pub struct PauseManager {
+    pub operation: PausableOperation,
+}
+
+#[contracttype]
+pub struct TimelockManager {
+    pub action_id: u64, // Named apart from WithdrawRequestManager so both keys never collide
+}
+
 #[contracttype]
 pub struct KeyManager {
     pub user: Address,
     pub key_id: u32,
 }
 
+#[contracttype]
+pub struct UserWithdrawIndexManager {
+    pub user: Address,
+}
+
+// Not a storage key itself, the extension ledger is keyed per user inside the manager
+pub struct UserTtlManager {
+    pub user: Address,
+}
+
+// Not a storage key itself, the limit and usage keys are built per token inside the manager
+pub struct WithdrawLimitManager {
+    pub token: Address,
+}
+
+#[contracttype]
+pub struct SettledTradeManager {
+    pub trade_id: u64,
+}
+
+#[contracttype]
+pub struct OrderFillManager {
+    pub order_hash: BytesN<32>,
+}
+
 #[contracttype]
 pub struct WithdrawRequestManager {
     pub id: u64,
@@ -62,13 +158,70 @@ pub enum WithdrawStatus {
     Requested,
     Rejected,
     Executed,
+    ForceWithdrawn,
+    Cancelled,
+    Queued, // Approved by the operator but held back by the token withdraw limit
+    Expired,
+}
+
+impl WithdrawStatus {
+    /// Whether the request still holds the user's funds on withdraw.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, WithdrawStatus::Requested | WithdrawStatus::Queued)
+    }
 }
 
 #[contracttype]
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
 pub struct WithdrawData {
     pub user: Address,
     pub token: Address,
     pub amount: i128,
     pub status: WithdrawStatus,
+    pub to: Address,
+    pub created_ledger: u32,
+}
+
+/// Reads a token or pair listing entry. Entries written by builds which kept the listings
+/// in the instance storage are read from there until they get written again.
+pub(crate) fn read_listing_entry<K, V>(e: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    if let Some(value) = e.storage().persistent().get::<K, V>(key) {
+        e.storage()
+            .persistent()
+            .bump(key, LISTING_BUMP_AMOUNT, LISTING_BUMP_AMOUNT);
+        Some(value)
+    } else {
+        e.storage().instance().get::<K, V>(key)
+    }
+}
+
+pub(crate) fn write_listing_entry<K, V>(e: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    e.storage().persistent().set(key, value);
+    e.storage()
+        .persistent()
+        .bump(key, LISTING_BUMP_AMOUNT, LISTING_BUMP_AMOUNT);
+
+    if e.storage().instance().has(key) {
+        e.storage().instance().remove(key);
+    }
+}
+
+/// Bumps a user data entry if it exists, bumping a missing entry would fail.
+pub(crate) fn bump_user_data<K>(e: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    if e.storage().persistent().has(key) {
+        e.storage()
+            .persistent()
+            .bump(key, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
 }
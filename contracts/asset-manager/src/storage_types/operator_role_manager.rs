@@ -0,0 +1,31 @@
+use super::{OperatorRole, OperatorRoleManager};
+use crate::error::Error;
+use soroban_sdk::{assert_with_error, Address, Env, Symbol};
+
+impl OperatorRoleManager {
+    pub fn new(operator: Address, role: OperatorRole) -> Self {
+        Self { operator, role }
+    }
+
+    pub fn has_role(&self, e: &Env) -> bool {
+        e.storage().instance().has(self)
+    }
+
+    pub fn grant_role(&self, e: &Env) {
+        // granting the same role twice is a no-op, so reject it like other repeated writes
+        assert_with_error!(e, !self.has_role(e), Error::ErrSameValueStored);
+
+        e.storage().instance().set(self, &true);
+    }
+
+    pub fn revoke_role(&self, e: &Env) {
+        assert_with_error!(e, self.has_role(e), Error::ErrSameValueStored);
+
+        e.storage().instance().remove(self);
+    }
+
+    pub fn emit_role_update(&self, e: &Env, granted: bool) {
+        let topics = (Symbol::new(e, "operator_role"), &self.operator);
+        e.events().publish(topics, (self.role, granted));
+    }
+}
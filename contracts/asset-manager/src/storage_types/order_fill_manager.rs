@@ -0,0 +1,71 @@
+use soroban_sdk::{assert_with_error, contracttype, BytesN, Env, Symbol};
+
+use crate::{error::Error, types::trade_upload::Order};
+
+use super::{OrderFillManager, USER_DATA_BUMP_AMOUNT};
+
+#[contracttype]
+#[derive(Clone)]
+pub struct OrderFill {
+    pub quantity: i128,
+    pub filled_quantity: i128,
+    pub filled_fee_amount: i128,
+}
+
+impl OrderFill {
+    pub fn remaining_quantity(&self) -> i128 {
+        self.quantity - self.filled_quantity
+    }
+}
+
+impl OrderFillManager {
+    pub fn new(order_hash: BytesN<32>) -> Self {
+        Self { order_hash }
+    }
+
+    pub fn read_order_fill(&self, e: &Env) -> Option<OrderFill> {
+        let order_fill = e.storage().persistent().get::<_, OrderFill>(self);
+        if order_fill.is_some() {
+            e.storage()
+                .persistent()
+                .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+        }
+
+        order_fill
+    }
+
+    /// Settles `quantity` against what is left of the signed order quantity,
+    /// a limit order can be filled across many trades and batches but never overfilled.
+    /// The signed fee limit holds for the whole order, not for each of its fills.
+    pub fn fill(&self, e: &Env, order: &Order, quantity: i128, fee_amount: i128) -> OrderFill {
+        let mut order_fill = self.read_order_fill(e).unwrap_or(OrderFill {
+            quantity: order.quantity,
+            filled_quantity: 0,
+            filled_fee_amount: 0,
+        });
+        assert_with_error!(
+            e,
+            quantity <= order_fill.remaining_quantity(),
+            Error::ErrOrderOverfilled
+        );
+        assert_with_error!(
+            e,
+            fee_amount <= order.max_fee_amount - order_fill.filled_fee_amount,
+            Error::ErrOrderFeeLimitExceeded
+        );
+        order_fill.filled_quantity += quantity;
+        order_fill.filled_fee_amount += fee_amount;
+
+        e.storage().persistent().set(self, &order_fill);
+        e.storage()
+            .persistent()
+            .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+
+        order_fill
+    }
+
+    pub fn emit_order_fill(&self, e: &Env, order_fill: OrderFill) {
+        let topics = (Symbol::new(e, "order_fill"), self.order_hash.clone());
+        e.events().publish(topics, order_fill);
+    }
+}
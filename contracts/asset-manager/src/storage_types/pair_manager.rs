@@ -1,9 +1,16 @@
-use super::{ListingStatus, PairManager};
+use super::{read_listing_entry, write_listing_entry, DataKey, ListingStatus, PairManager};
 use crate::error::Error;
 use soroban_sdk::{
-    assert_with_error, contracttype, panic_with_error, Address, Env, String, Symbol,
+    assert_with_error, contracttype, panic_with_error, Address, Env, String, Symbol, Vec,
 };
 
+pub(crate) const MAX_PRICE_DECIMALS: u32 = 18;
+
+#[contracttype]
+enum PairPriceKey {
+    PriceDecimals(String),
+}
+
 #[contracttype]
 #[derive(PartialEq)]
 pub struct PairStorageInfo {
@@ -24,6 +31,10 @@ impl PairStorageInfo {
     pub fn get_pair(self) -> (Address, Address) {
         (self.token1, self.token2)
     }
+
+    pub fn status(&self) -> ListingStatus {
+        self.status.clone()
+    }
 }
 
 impl PairManager {
@@ -31,25 +42,37 @@ impl PairManager {
         Self { symbol }
     }
 
+    pub fn read_registry(e: &Env) -> Vec<String> {
+        read_listing_entry(e, &DataKey::PairRegistry).unwrap_or(Vec::new(e))
+    }
+
+    pub fn read_pair_info(&self, e: &Env) -> Option<PairStorageInfo> {
+        read_listing_entry(e, self)
+    }
+
     pub fn is_listed(&self, e: &Env) -> bool {
-        e.storage()
-            .instance()
-            .get::<_, PairStorageInfo>(self)
-            .map_or(false, |pair_storage| {
-                matches!(pair_storage.status, ListingStatus::Listed)
-            })
+        self.read_pair_info(e)
+            .is_some_and(|pair_storage| matches!(pair_storage.status, ListingStatus::Listed))
     }
 
-    pub fn get_pair(&self, e: &Env) -> (Address, Address) {
-        if let Some(value) = e.storage().instance().get::<_, PairStorageInfo>(self) {
-            (value.token1, value.token2)
-        } else {
-            panic_with_error!(e, Error::ErrFinalized)
+    /// Returns the pair tokens if the pair status lets it be traded.
+    pub fn get_tradable_pair(&self, e: &Env) -> (Address, Address) {
+        match self.read_pair_info(e) {
+            Some(value) if value.status != ListingStatus::Delisted => {
+                assert_with_error!(
+                    e,
+                    value.status.allows_trading(),
+                    Error::ErrTradingNotAllowed
+                );
+                (value.token1, value.token2)
+            }
+            _ => panic_with_error!(e, Error::ErrPairIsNotListed),
         }
     }
 
     pub fn set_pair_info(&self, e: &Env, pair_info: &PairStorageInfo) {
-        if let Some(stored_value) = e.storage().instance().get::<_, PairStorageInfo>(self) {
+        let stored_value = self.read_pair_info(e);
+        if let Some(stored_value) = &stored_value {
             // check for the same value have been already stored
             // it's cheaper in gas to assert than rewrite the value
             assert_with_error!(
@@ -71,7 +94,44 @@ impl PairManager {
             Error::ErrSamePairTokens
         );
 
-        e.storage().instance().set(self, pair_info);
+        // legacy pairs listed before the registry existed get registered on their next update
+        let mut registry = Self::read_registry(e);
+        if !registry.contains(&self.symbol) {
+            registry.push_back(self.symbol.clone());
+            write_listing_entry(e, &DataKey::PairRegistry, &registry);
+        }
+
+        write_listing_entry(e, self, pair_info);
+    }
+
+    /// Prices are quote token units per base token unit scaled by `10^price_decimals`,
+    /// pairs which were never configured trade at plain unit prices.
+    pub fn read_price_decimals(&self, e: &Env) -> u32 {
+        read_listing_entry(e, &PairPriceKey::PriceDecimals(self.symbol.clone())).unwrap_or(0)
+    }
+
+    pub fn set_price_decimals(&self, e: &Env, price_decimals: u32) {
+        assert_with_error!(
+            e,
+            self.read_pair_info(e).is_some(),
+            Error::ErrPairIsNotListed
+        );
+        assert_with_error!(
+            e,
+            price_decimals <= MAX_PRICE_DECIMALS,
+            Error::ErrInvalidPriceDecimals
+        );
+
+        write_listing_entry(
+            e,
+            &PairPriceKey::PriceDecimals(self.symbol.clone()),
+            &price_decimals,
+        );
+    }
+
+    pub fn emit_price_decimals(&self, e: &Env, price_decimals: u32) {
+        let topics = (Symbol::new(e, "pair_price_decimals"), self.symbol.to_val());
+        e.events().publish(topics, price_decimals);
     }
 
     pub fn emit_listing_status(&self, e: &Env, pair: (Address, Address), status: ListingStatus) {
@@ -0,0 +1,39 @@
+use super::{PausableOperation, PauseManager};
+use crate::error::Error;
+use soroban_sdk::{assert_with_error, Env, Symbol};
+
+impl PauseManager {
+    pub fn new(operation: PausableOperation) -> Self {
+        Self { operation }
+    }
+
+    pub fn is_paused(&self, e: &Env) -> bool {
+        let is_stored_paused = |operation| {
+            e.storage()
+                .instance()
+                .get::<_, bool>(&Self::new(operation))
+                .unwrap_or(false)
+        };
+
+        is_stored_paused(self.operation) || is_stored_paused(PausableOperation::All)
+    }
+
+    pub fn assert_not_paused(&self, e: &Env) {
+        assert_with_error!(e, !self.is_paused(e), Error::ErrOperationPaused);
+    }
+
+    pub fn set_paused(&self, e: &Env, paused: bool) {
+        if let Some(stored_value) = e.storage().instance().get::<_, bool>(self) {
+            // check for the same value have been already stored
+            // it's cheaper in gas to assert than rewrite the value
+            assert_with_error!(e, paused != stored_value, Error::ErrSameValueStored);
+        }
+
+        e.storage().instance().set(self, &paused);
+    }
+
+    pub fn emit_pause_status(&self, e: &Env, paused: bool) {
+        let topics = (Symbol::new(e, "pause_status"), self.operation);
+        e.events().publish(topics, paused);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{KeyManager, USER_DATA_BUMP_AMOUNT};
+use super::{bump_user_data, KeyManager, USER_DATA_BUMP_AMOUNT};
 use crate::error::Error;
 use soroban_sdk::{panic_with_error, Address, BytesN, Env, Symbol};
 
@@ -27,9 +27,16 @@ impl KeyManager {
             panic_with_error!(e, Error::ErrPublicKeyAlreadyExist)
         } else {
             e.storage().persistent().set(self, public_key);
+            e.storage()
+                .persistent()
+                .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
         }
     }
 
+    pub fn bump(&self, e: &Env) {
+        bump_user_data(e, self);
+    }
+
     pub fn emit_announce_key_event(&self, e: &Env, public_key: BytesN<32>) {
         let topics = (Symbol::new(e, "announce_key"), &self.user);
         e.events().publish(topics, (self.key_id, public_key));
@@ -0,0 +1,25 @@
+use soroban_sdk::{assert_with_error, Env};
+
+use crate::error::Error;
+
+use super::{SettledTradeManager, USER_DATA_BUMP_AMOUNT};
+
+impl SettledTradeManager {
+    pub fn new(trade_id: u64) -> Self {
+        Self { trade_id }
+    }
+
+    /// Records the trade as settled, a trade id can only be uploaded once.
+    pub fn mark_settled(&self, e: &Env) {
+        assert_with_error!(
+            e,
+            !e.storage().persistent().has(self),
+            Error::ErrTradeAlreadySettled
+        );
+
+        e.storage().persistent().set(self, &true);
+        e.storage()
+            .persistent()
+            .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
+}
@@ -0,0 +1,62 @@
+use soroban_sdk::{contracttype, panic_with_error, Env, Symbol};
+
+use crate::{error::Error, types::AdminAction};
+
+use super::{TimelockManager, USER_DATA_BUMP_AMOUNT};
+
+/// Queued actions are bumped for `USER_DATA_BUMP_AMOUNT` ledgers,
+/// the delay leaves at least half of it to execute them.
+pub(crate) const MAX_TIMELOCK_DELAY: u32 = USER_DATA_BUMP_AMOUNT / 2;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct QueuedAdminAction {
+    pub action: AdminAction,
+    pub eta: u32, // ledger sequence from which the action can be executed
+}
+
+impl TimelockManager {
+    pub fn new(action_id: u64) -> Self {
+        Self { action_id }
+    }
+
+    pub fn read_queued_action(&self, e: &Env) -> Option<QueuedAdminAction> {
+        let queued_action = e.storage().persistent().get::<_, QueuedAdminAction>(self);
+        if queued_action.is_some() {
+            e.storage()
+                .persistent()
+                .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+        }
+        queued_action
+    }
+
+    pub fn get_queued_action(&self, e: &Env) -> QueuedAdminAction {
+        if let Some(queued_action) = self.read_queued_action(e) {
+            queued_action
+        } else {
+            panic_with_error!(e, Error::ErrAdminActionNotQueued)
+        }
+    }
+
+    pub fn write_queued_action(&self, e: &Env, queued_action: &QueuedAdminAction) {
+        e.storage().persistent().set(self, queued_action);
+        e.storage()
+            .persistent()
+            .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
+
+    pub fn remove_queued_action(&self, e: &Env) {
+        e.storage().persistent().remove(self);
+    }
+
+    pub fn emit_admin_action_queued(&self, e: &Env, queued_action: QueuedAdminAction) {
+        let topics = (Symbol::new(e, "admin_action_queued"), self.action_id);
+        e.events()
+            .publish(topics, (queued_action.action, queued_action.eta));
+    }
+
+    pub fn emit_admin_action_status(&self, e: &Env, event: &str, queued_action: QueuedAdminAction) {
+        let topics = (Symbol::new(e, event), self.action_id);
+        e.events().publish(topics, queued_action.action);
+    }
+}
@@ -1,31 +1,210 @@
-use super::{ListingStatus, TokenManager};
+use super::{
+    read_listing_entry, write_listing_entry, DataKey, ListingStatus, TokenManager,
+    LISTING_BUMP_AMOUNT,
+};
 use crate::Error;
-use soroban_sdk::{assert_with_error, Address, Env, Symbol};
+use soroban_sdk::{
+    assert_with_error, contracttype, panic_with_error, token, Address, Env, Symbol, TryFromVal,
+    Val, Vec,
+};
+
+#[contracttype]
+#[derive(PartialEq, Clone)]
+pub struct DepositLimits {
+    pub min_deposit: i128,      // smallest amount accepted by a single deposit
+    pub max_user_balance: i128, // max amount a single user can hold in the contract
+    pub deposit_cap: i128,      // max amount of the token the contract can hold
+}
+
+impl DepositLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            min_deposit: 0,
+            max_user_balance: i128::MAX,
+            deposit_cap: i128::MAX,
+        }
+    }
+}
+
+#[contracttype]
+enum TokenKey {
+    Deposited(Address), // i128 amount users deposited and did not withdraw yet
+}
+
+#[contracttype]
+#[derive(PartialEq, Clone)]
+pub struct TokenConfig {
+    pub status: ListingStatus,
+    pub decimals: u32, // token decimals at the time it was first listed
+    pub limits: DepositLimits,
+}
 
 impl TokenManager {
     pub fn new(token: Address) -> Self {
         Self { token }
     }
 
+    /// Tokens listed before the config was introduced only have their `ListingStatus` stored,
+    /// such entries get the current decimals and no deposit limits, written back on first read.
+    pub fn read_config(&self, e: &Env) -> Option<TokenConfig> {
+        let value = read_listing_entry::<_, Val>(e, self)?;
+
+        if let Ok(config) = TokenConfig::try_from_val(e, &value) {
+            return Some(config);
+        }
+
+        let status = ListingStatus::try_from_val(e, &value).ok()?;
+        let config = self.new_config(e, status);
+        self.write_config(e, &config);
+
+        Some(config)
+    }
+
+    pub fn read_registry(e: &Env) -> Vec<Address> {
+        read_listing_entry(e, &DataKey::TokenRegistry).unwrap_or(Vec::new(e))
+    }
+
+    pub fn read_status(&self, e: &Env) -> Option<ListingStatus> {
+        self.read_config(e).map(|config| config.status)
+    }
+
     pub fn is_listed(&self, e: &Env) -> bool {
-        e.storage()
-            .instance()
-            .get::<_, ListingStatus>(self)
-            .map_or(false, |status| matches!(status, ListingStatus::Listed))
+        self.read_status(e) == Some(ListingStatus::Listed)
     }
 
     pub fn set_listing_status(&self, e: &Env, status: &ListingStatus) {
-        if let Some(stored_value) = e.storage().instance().get::<_, ListingStatus>(self) {
+        let config = if let Some(mut stored_config) = self.read_config(e) {
             // check for the same value have been already stored
             // it's cheaper in gas to assert than rewrite the value
-            assert_with_error!(e, *status != stored_value, Error::ErrSameValueStored);
-        }
+            assert_with_error!(
+                e,
+                *status != stored_config.status,
+                Error::ErrSameValueStored
+            );
+            stored_config.status = status.clone();
+            stored_config
+        } else {
+            self.new_config(e, status.clone())
+        };
 
-        e.storage().instance().set(self, status);
+        self.write_config(e, &config);
+    }
+
+    pub fn set_deposit_limits(&self, e: &Env, limits: &DepositLimits) {
+        let mut config = self.read_existing_config(e);
+
+        config.limits = limits.clone();
+
+        self.write_config(e, &config);
+    }
+
+    /// Checks the deposit against the token limits, `user_holdings` being
+    /// everything the user already holds in the contract for this token.
+    pub fn assert_deposit_allowed(&self, e: &Env, user_holdings: i128, amount: i128) {
+        let config = self.read_existing_config(e);
+        assert_with_error!(
+            e,
+            config.status.allows_deposit(),
+            Error::ErrDepositNotAllowed
+        );
+
+        let limits = config.limits;
+        assert_with_error!(
+            e,
+            amount >= limits.min_deposit,
+            Error::ErrDepositBelowMinimum
+        );
+        assert_with_error!(
+            e,
+            user_holdings + amount <= limits.max_user_balance,
+            Error::ErrUserBalanceCapExceeded
+        );
+
+        assert_with_error!(
+            e,
+            self.read_deposited(e) + amount <= limits.deposit_cap,
+            Error::ErrDepositCapExceeded
+        );
+    }
+
+    /// Amount users deposited and did not withdraw yet, which the deposit cap applies to.
+    /// Tokens sent to the contract directly are not part of it.
+    pub fn read_deposited(&self, e: &Env) -> i128 {
+        e.storage()
+            .persistent()
+            .get::<_, i128>(&TokenKey::Deposited(self.token.clone()))
+            .unwrap_or(0)
+    }
+
+    pub fn add_deposited(&self, e: &Env, amount: i128) {
+        self.write_deposited(e, self.read_deposited(e) + amount);
+    }
+
+    /// Deposits made before the total was tracked are not part of it,
+    /// withdrawing them only brings the total down to zero.
+    pub fn remove_deposited(&self, e: &Env, amount: i128) {
+        self.write_deposited(e, (self.read_deposited(e) - amount).max(0));
+    }
+
+    /// Every listing state allows withdrawing, only tokens which were never listed are rejected.
+    pub fn assert_withdraw_allowed(&self, e: &Env) {
+        assert_with_error!(e, self.read_status(e).is_some(), Error::ErrTokenIsNotListed);
+    }
+
+    pub fn assert_trading_allowed(&self, e: &Env) {
+        match self.read_status(e) {
+            Some(status) if status != ListingStatus::Delisted => {
+                assert_with_error!(e, status.allows_trading(), Error::ErrTradingNotAllowed);
+            }
+            _ => panic_with_error!(e, Error::ErrTradeTokenIsNotListed),
+        }
     }
 
     pub fn emit_listing_status(&self, e: &Env, status: ListingStatus) {
         let topics = (Symbol::new(e, "token_listing"), &self.token);
         e.events().publish(topics, status);
     }
+
+    pub fn emit_deposit_limits(&self, e: &Env, limits: DepositLimits) {
+        let topics = (Symbol::new(e, "token_deposit_limits"), &self.token);
+        e.events().publish(topics, limits);
+    }
+
+    /// Tokens are added to the registry the first time their config is written,
+    /// which for legacy entries happens when they are first read.
+    fn write_config(&self, e: &Env, config: &TokenConfig) {
+        let is_registered = read_listing_entry::<_, Val>(e, self)
+            .is_some_and(|value| TokenConfig::try_from_val(e, &value).is_ok());
+
+        if !is_registered {
+            let mut registry = Self::read_registry(e);
+            registry.push_back(self.token.clone());
+            write_listing_entry(e, &DataKey::TokenRegistry, &registry);
+        }
+
+        write_listing_entry(e, self, config);
+    }
+
+    fn write_deposited(&self, e: &Env, deposited: i128) {
+        let key = TokenKey::Deposited(self.token.clone());
+        e.storage().persistent().set(&key, &deposited);
+        e.storage()
+            .persistent()
+            .bump(&key, LISTING_BUMP_AMOUNT, LISTING_BUMP_AMOUNT);
+    }
+
+    fn read_existing_config(&self, e: &Env) -> TokenConfig {
+        let Some(config) = self.read_config(e) else {
+            panic_with_error!(e, Error::ErrTokenIsNotListed)
+        };
+        config
+    }
+
+    fn new_config(&self, e: &Env, status: ListingStatus) -> TokenConfig {
+        TokenConfig {
+            status,
+            decimals: token::Client::new(e, &self.token).decimals(),
+            limits: DepositLimits::unlimited(),
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use soroban_sdk::{contracttype, Address, Env, Symbol};
 
-use super::{UserBalanceManager, USER_DATA_BUMP_AMOUNT};
+use super::{bump_user_data, UserBalanceManager, USER_DATA_BUMP_AMOUNT};
 
 #[contracttype]
 pub struct UserBalances {
@@ -43,8 +43,17 @@ impl UserBalanceManager {
         self.write_user_balance(e, &modified_balances);
     }
 
+    pub fn bump(&self, e: &Env) {
+        bump_user_data(e, self);
+    }
+
     pub fn emit_deposit(&self, e: &Env, amount: i128) {
         let topics = (Symbol::new(e, "deposit"), &self.user, &self.token);
         e.events().publish(topics, amount);
     }
+
+    pub fn emit_deposit_for(&self, e: &Env, from: &Address, amount: i128) {
+        let topics = (Symbol::new(e, "deposit_for"), from, &self.user, &self.token);
+        e.events().publish(topics, amount);
+    }
 }
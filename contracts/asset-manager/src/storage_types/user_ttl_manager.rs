@@ -0,0 +1,35 @@
+use soroban_sdk::{contracttype, Address, Env};
+
+use super::{UserTtlManager, USER_DATA_BUMP_AMOUNT};
+
+#[contracttype]
+enum UserTtlKey {
+    ExtendedLedger(Address),
+}
+
+impl UserTtlManager {
+    pub fn new(user: Address) -> Self {
+        Self { user }
+    }
+
+    /// User data is always bumped by `USER_DATA_BUMP_AMOUNT`, so the ledger of the
+    /// last extension is enough to know until when the extended entries live.
+    pub fn write_extended_ledger(&self, e: &Env) {
+        let key = UserTtlKey::ExtendedLedger(self.user.clone());
+
+        e.storage().persistent().set(&key, &e.ledger().sequence());
+        e.storage()
+            .persistent()
+            .bump(&key, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
+
+    /// Returns 0 for users whose data was never extended.
+    pub fn read_extended_until(&self, e: &Env) -> u32 {
+        e.storage()
+            .persistent()
+            .get::<_, u32>(&UserTtlKey::ExtendedLedger(self.user.clone()))
+            .map_or(0, |extended_ledger| {
+                extended_ledger.saturating_add(USER_DATA_BUMP_AMOUNT)
+            })
+    }
+}
@@ -0,0 +1,83 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use super::{
+    bump_user_data, UserWithdrawIndexManager, WithdrawRequestManager, USER_DATA_BUMP_AMOUNT,
+};
+
+#[contracttype]
+struct UserWithdrawIndexEntry {
+    user: Address,
+    index: u32,
+}
+
+impl UserWithdrawIndexManager {
+    pub fn new(user: Address) -> Self {
+        Self { user }
+    }
+
+    pub fn read_count(&self, e: &Env) -> u32 {
+        if let Some(count) = e.storage().persistent().get::<_, u32>(self) {
+            e.storage()
+                .persistent()
+                .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+            count
+        } else {
+            0
+        }
+    }
+
+    /// Appends the id at the end of the user index, so ids are listed in the order
+    /// they were requested.
+    pub fn push_withdraw_id(&self, e: &Env, id: u64) {
+        let count = self.read_count(e);
+        let entry = self.entry(count);
+
+        e.storage().persistent().set(&entry, &id);
+        e.storage()
+            .persistent()
+            .bump(&entry, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+
+        e.storage().persistent().set(self, &(count + 1));
+        e.storage()
+            .persistent()
+            .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
+
+    pub fn read_withdraw_ids(&self, e: &Env, start: u32, limit: u32) -> Vec<u64> {
+        let end = self.read_count(e).min(start.saturating_add(limit));
+        let mut ids = Vec::new(e);
+
+        for index in start..end {
+            let entry = self.entry(index);
+            if let Some(id) = e.storage().persistent().get::<_, u64>(&entry) {
+                e.storage()
+                    .persistent()
+                    .bump(&entry, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+                ids.push_back(id);
+            }
+        }
+
+        ids
+    }
+
+    /// Bumps the index along with its last `limit` entries and their requests,
+    /// older requests are the ones the most likely to be processed already.
+    pub fn bump_recent(&self, e: &Env, limit: u32) {
+        let count = self.read_count(e);
+
+        for index in count.saturating_sub(limit)..count {
+            let entry = self.entry(index);
+            if let Some(id) = e.storage().persistent().get::<_, u64>(&entry) {
+                bump_user_data(e, &entry);
+                WithdrawRequestManager::new(id).bump(e);
+            }
+        }
+    }
+
+    fn entry(&self, index: u32) -> UserWithdrawIndexEntry {
+        UserWithdrawIndexEntry {
+            user: self.user.clone(),
+            index,
+        }
+    }
+}
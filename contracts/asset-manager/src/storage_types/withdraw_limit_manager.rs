@@ -0,0 +1,128 @@
+use soroban_sdk::{contracttype, Address, Env, Map, Symbol};
+
+use super::{WithdrawLimitManager, USER_DATA_BUMP_AMOUNT};
+
+#[contracttype]
+#[derive(PartialEq, Clone)]
+pub struct WithdrawLimit {
+    pub window: u32,      // amount of ledgers the caps below apply to
+    pub user_cap: i128,   // max amount a single user can withdraw within the window
+    pub global_cap: i128, // max amount withdrawn by all users within the window
+}
+
+/// Amount of buckets a window is split into, usage expires one bucket at a time.
+const WINDOW_BUCKETS: u32 = 10;
+
+#[contracttype]
+struct WithdrawUsage {
+    bucket_size: u32,        // amount of ledgers covered by a single bucket
+    buckets: Map<u32, i128>, // bucket index => amount withdrawn within the bucket
+}
+
+#[contracttype]
+enum WithdrawLimitKey {
+    Limit(Address),
+    GlobalUsage(Address),
+    UserUsage(Address, Address),
+}
+
+impl WithdrawLimitManager {
+    pub fn new(token: Address) -> Self {
+        Self { token }
+    }
+
+    pub fn read_limit(&self, e: &Env) -> Option<WithdrawLimit> {
+        e.storage()
+            .instance()
+            .get::<_, WithdrawLimit>(&WithdrawLimitKey::Limit(self.token.clone()))
+    }
+
+    pub fn set_limit(&self, e: &Env, limit: &WithdrawLimit) {
+        e.storage()
+            .instance()
+            .set(&WithdrawLimitKey::Limit(self.token.clone()), limit);
+    }
+
+    /// Usage tracked so far is kept, it still counts once a limit with the same window is set again.
+    pub fn remove_limit(&self, e: &Env) {
+        e.storage()
+            .instance()
+            .remove(&WithdrawLimitKey::Limit(self.token.clone()));
+    }
+
+    /// Accounts the amount against both caps of the rolling window ending at the current ledger.
+    /// Returns false, leaving the usage untouched, if either cap would be exceeded.
+    /// Usage is tracked in buckets of `window / WINDOW_BUCKETS` ledgers, so a withdrawn amount
+    /// counts for at least `window` ledgers and at most one bucket longer.
+    pub fn try_consume(&self, e: &Env, user: &Address, amount: i128) -> bool {
+        let Some(limit) = self.read_limit(e) else {
+            return true;
+        };
+
+        let global_key = WithdrawLimitKey::GlobalUsage(self.token.clone());
+        let user_key = WithdrawLimitKey::UserUsage(self.token.clone(), user.clone());
+
+        let mut global_usage = Self::read_usage(e, &global_key, limit.window);
+        let mut user_usage = Self::read_usage(e, &user_key, limit.window);
+
+        if Self::used_amount(&global_usage) + amount > limit.global_cap
+            || Self::used_amount(&user_usage) + amount > limit.user_cap
+        {
+            return false;
+        }
+
+        Self::add_usage(e, &mut global_usage, amount);
+        Self::add_usage(e, &mut user_usage, amount);
+
+        Self::write_usage(e, &global_key, &global_usage);
+        Self::write_usage(e, &user_key, &user_usage);
+
+        true
+    }
+
+    pub fn emit_limit_update(&self, e: &Env, limit: Option<WithdrawLimit>) {
+        let topics = (Symbol::new(e, "withdraw_limit"), &self.token);
+        e.events().publish(topics, limit);
+    }
+
+    /// Reads the usage with the buckets that left the window dropped.
+    /// Usage tracked with another bucket size, i.e. under a previous window, is dropped as a whole.
+    fn read_usage(e: &Env, key: &WithdrawLimitKey, window: u32) -> WithdrawUsage {
+        let bucket_size = window.div_ceil(WINDOW_BUCKETS);
+        let current_bucket = e.ledger().sequence() / bucket_size;
+
+        let mut buckets = Map::new(e);
+
+        if let Some(usage) = e.storage().persistent().get::<_, WithdrawUsage>(key) {
+            if usage.bucket_size == bucket_size {
+                for (index, amount) in usage.buckets {
+                    if index.saturating_add(WINDOW_BUCKETS) >= current_bucket {
+                        buckets.set(index, amount);
+                    }
+                }
+            }
+        }
+
+        WithdrawUsage {
+            bucket_size,
+            buckets,
+        }
+    }
+
+    fn used_amount(usage: &WithdrawUsage) -> i128 {
+        usage.buckets.values().iter().sum()
+    }
+
+    fn add_usage(e: &Env, usage: &mut WithdrawUsage, amount: i128) {
+        let current_bucket = e.ledger().sequence() / usage.bucket_size;
+        let used = usage.buckets.get(current_bucket).unwrap_or(0);
+        usage.buckets.set(current_bucket, used + amount);
+    }
+
+    fn write_usage(e: &Env, key: &WithdrawLimitKey, usage: &WithdrawUsage) {
+        e.storage().persistent().set(key, usage);
+        e.storage()
+            .persistent()
+            .bump(key, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
+}
@@ -1,8 +1,46 @@
-use soroban_sdk::{assert_with_error, panic_with_error, Env, Symbol};
+use soroban_sdk::{
+    assert_with_error, contracttype, panic_with_error, symbol_short, Address, Env, Map, Symbol,
+    TryFromVal, Val,
+};
 
 use crate::error::Error;
 
-use super::{WithdrawData, WithdrawRequestManager, USER_DATA_BUMP_AMOUNT};
+use super::{
+    bump_user_data, DataKey, WithdrawData, WithdrawRequestManager, WithdrawStatus,
+    USER_DATA_BUMP_AMOUNT,
+};
+
+/// Layout of the requests written by builds preceding the `to` and `created_ledger` fields.
+#[contracttype]
+#[derive(PartialEq, Clone)]
+pub(crate) struct LegacyWithdrawData {
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: WithdrawStatus,
+}
+
+impl LegacyWithdrawData {
+    /// Legacy requests pay out to their user. The ledger they were created at is unknown,
+    /// so they date from the migration to version 1, or from now until it has run,
+    /// which gives the operator the full delays to process them after the upgrade.
+    fn into_withdraw_data(self, e: &Env) -> WithdrawData {
+        let created_ledger = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::MigrationLedger)
+            .unwrap_or(e.ledger().sequence());
+
+        WithdrawData {
+            to: self.user.clone(),
+            user: self.user,
+            token: self.token,
+            amount: self.amount,
+            status: self.status,
+            created_ledger,
+        }
+    }
+}
 
 impl WithdrawRequestManager {
     pub fn new(id: u64) -> Self {
@@ -10,7 +48,7 @@ impl WithdrawRequestManager {
     }
 
     pub fn read_withdraw_request(&self, e: &Env) -> WithdrawData {
-        if let Some(data) = e.storage().persistent().get::<_, WithdrawData>(self) {
+        if let Some(data) = self.get_withdraw_request(e) {
             e.storage()
                 .persistent()
                 .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
@@ -20,18 +58,64 @@ impl WithdrawRequestManager {
         }
     }
 
+    /// Reads the request the user is still waiting on, as the user is the only one
+    /// allowed to act on it besides the operator.
+    pub fn read_user_pending_request(&self, e: &Env, user: &Address) -> WithdrawData {
+        let withdraw_request = self.read_withdraw_request(e);
+
+        assert_with_error!(
+            e,
+            withdraw_request.user == *user,
+            Error::ErrWithdrawRequestDataMismatch
+        );
+        assert_with_error!(
+            e,
+            withdraw_request.status.is_pending(),
+            Error::ErrWithdrawRequestAlreadyProcessed
+        );
+
+        withdraw_request
+    }
+
     pub fn write_withdraw_request(&self, e: &Env, withdraw_data: &WithdrawData) {
-        if let Some(data) = e.storage().persistent().get::<_, WithdrawData>(self) {
+        if let Some(data) = self.get_withdraw_request(e) {
             assert_with_error!(e, data != *withdraw_data, Error::ErrSameWithdrawDataExist);
         }
         e.storage().persistent().set(self, withdraw_data);
+        e.storage()
+            .persistent()
+            .bump(self, USER_DATA_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT);
+    }
+
+    pub fn bump(&self, e: &Env) {
+        bump_user_data(e, self);
     }
 
     pub fn emit_withdraw_request(&self, e: &Env, withdraw_data: WithdrawData) {
         let topics = (Symbol::new(e, "withdraw_request"), &withdraw_data.token);
         e.events().publish(
             topics,
-            (self.id, withdraw_data.amount, withdraw_data.status),
+            (
+                self.id,
+                withdraw_data.amount,
+                withdraw_data.status,
+                withdraw_data.to,
+            ),
         );
     }
+
+    /// Legacy requests are converted on read and stored in the current layout on their next write.
+    /// The layout is told apart by its fields, as decoding into the wrong one would trap.
+    pub fn get_withdraw_request(&self, e: &Env) -> Option<WithdrawData> {
+        let value = e.storage().persistent().get::<_, Val>(self)?;
+        let fields = Map::<Symbol, Val>::try_from_val(e, &value).ok()?;
+
+        if fields.contains_key(symbol_short!("to")) {
+            WithdrawData::try_from_val(e, &value).ok()
+        } else {
+            LegacyWithdrawData::try_from_val(e, &value)
+                .ok()
+                .map(|data| data.into_withdraw_data(e))
+        }
+    }
 }
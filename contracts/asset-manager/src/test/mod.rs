@@ -4,18 +4,26 @@ use ed25519_dalek::{Signer, SigningKey};
 use rand::rngs::OsRng;
 use soroban_sdk::{
     testutils::{Address as AddressTestTrait, Ledger},
-    token, Address, Bytes, BytesN, Env, String,
+    token, vec, Address, Bytes, BytesN, Env, String,
 };
 
 use crate::{
-    storage_types::{user_balance_manager::UserBalances, ListingStatus},
+    storage_types::{
+        user_balance_manager::UserBalances, ListingStatus, OperatorRole, PausableOperation,
+        WithdrawStatus,
+    },
     test_utils::{register_test_contract, AssetManager},
     types::{
         ExecutionWithdrawData, OperatorAction, OperatorWithdrawStatus, ValidateUserSignatureData,
     },
 };
 
+mod storage_ttl;
+mod timelock;
+mod token_config;
 mod trade_upload;
+mod upgrade;
+mod withdraw_limit;
 
 const DEFAULT_PAIR: &str = "SPOT_TKN1_TKN2";
 
@@ -100,6 +108,10 @@ impl<'a> Setup<'a> {
     }
 
     pub fn with_default_listed_pair(&self) -> &Self {
+        self.with_default_pair_status(&ListingStatus::Listed)
+    }
+
+    pub fn with_default_pair_status(&self, status: &ListingStatus) -> &Self {
         self.asset_manager
             .client()
             .mock_all_auths()
@@ -107,7 +119,7 @@ impl<'a> Setup<'a> {
                 &String::from_slice(&self.env, DEFAULT_PAIR),
                 &self.token.address.clone(),
                 &self.token2.address.clone(),
-                &ListingStatus::Listed,
+                status,
             );
         self
     }
@@ -188,6 +200,124 @@ fn check_initialized() {
     );
 }
 
+#[test]
+fn check_owner_transfer() {
+    let setup = Setup::new();
+    let new_owner = Address::random(&setup.env);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .propose_owner(&new_owner);
+
+    assert_eq!(setup.env.auths()[0].0, setup.owner);
+    assert_eq!(
+        setup.asset_manager.client().pending_owner(),
+        Some(new_owner.clone())
+    );
+    // ownership is not changed until the new owner accepts it
+    assert_eq!(setup.asset_manager.client().owner(), setup.owner);
+
+    setup.asset_manager.client().mock_all_auths().accept_owner();
+
+    assert_eq!(setup.env.auths()[0].0, new_owner);
+    assert_eq!(setup.asset_manager.client().owner(), new_owner);
+    assert_eq!(setup.asset_manager.client().pending_owner(), None);
+}
+
+#[test]
+fn check_owner_transfer_cancelled() {
+    let setup = Setup::new();
+    let new_owner = Address::random(&setup.env);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .propose_owner(&new_owner);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .cancel_owner_transfer();
+
+    assert_eq!(setup.asset_manager.client().pending_owner(), None);
+    assert_eq!(setup.asset_manager.client().owner(), setup.owner);
+}
+
+#[test]
+#[should_panic(expected = "18")]
+fn check_accept_owner_fail_without_proposal() {
+    let setup = Setup::new();
+
+    setup.asset_manager.client().mock_all_auths().accept_owner();
+}
+
+#[test]
+fn check_set_operator_manager() {
+    let setup = Setup::new();
+    let new_operator = Address::random(&setup.env);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_operator_manager(&new_operator);
+
+    assert_eq!(setup.env.auths()[0].0, setup.owner);
+    assert_eq!(
+        setup.asset_manager.client().operator_manager(),
+        new_operator
+    );
+}
+
+#[test]
+fn check_set_fee_collector_keeps_accrued_balances() {
+    let setup = Setup::new();
+    let new_fee_collector = Address::random(&setup.env);
+
+    setup.with_default_listed_tokens();
+
+    // fees accrued by the previous collector live on its regular user balance
+    setup.asset_manager.client().mock_all_auths().deposit(
+        &setup.user1,
+        &setup.fee_token.address,
+        &3,
+    );
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_fee_collector(&setup.user1);
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_fee_collector(&new_fee_collector);
+
+    assert_eq!(
+        setup.asset_manager.client().fee_collector(),
+        new_fee_collector
+    );
+    assert_eq!(
+        setup
+            .asset_manager
+            .client()
+            .balances(&setup.user1, &setup.fee_token.address)
+            .balance,
+        3
+    );
+
+    // the previous collector is still able to withdraw its fees
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.fee_token.address, &3, &None);
+}
+
 #[test]
 #[should_panic(expected = "6")]
 fn check_deposit_fail_for_unsupported_token() {
@@ -200,6 +330,184 @@ fn check_deposit_fail_for_unsupported_token() {
         .deposit(&setup.user1, &setup.token.address, &10);
 }
 
+#[test]
+fn check_deposit_for_beneficiary() {
+    let setup = Setup::new();
+
+    setup.with_default_listed_tokens();
+
+    setup.asset_manager.client().mock_all_auths().deposit_for(
+        &setup.user1,
+        &setup.user2,
+        &setup.token.address,
+        &6,
+    );
+
+    assert_eq!(setup.env.auths()[0].0, setup.user1);
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+    assert_eq!(
+        setup
+            .asset_manager
+            .client()
+            .balances(&setup.user2, &setup.token.address)
+            .balance,
+        6
+    );
+    assert_eq!(
+        setup
+            .asset_manager
+            .client()
+            .balances(&setup.user1, &setup.token.address)
+            .balance,
+        0
+    );
+}
+
+#[test]
+fn check_withdraw_request_allowed_under_every_status() {
+    for status in [
+        ListingStatus::Listed,
+        ListingStatus::Delisted,
+        ListingStatus::DepositsPaused,
+        ListingStatus::TradingHalted,
+    ] {
+        let setup = Setup::new();
+        let client = setup.asset_manager.client();
+
+        setup
+            .with_default_listed_tokens()
+            .with_default_deposit(10, 5);
+
+        if status != ListingStatus::Listed {
+            client
+                .mock_all_auths()
+                .set_token_status(&setup.token.address, &status);
+        }
+
+        let id = client.mock_all_auths().request_withdraw(
+            &setup.user1,
+            &setup.token.address,
+            &10,
+            &None,
+        );
+
+        assert!(client.get_withdraw_request(&id).status == WithdrawStatus::Requested);
+    }
+}
+
+#[test]
+#[should_panic(expected = "6")]
+fn check_request_withdraw_fail_on_unlisted_token() {
+    let setup = Setup::new();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &10, &None);
+}
+
+#[test]
+#[should_panic(expected = "34")]
+fn check_deposit_fail_on_deposits_paused_token() {
+    let setup = Setup::new();
+
+    setup.with_default_listed_tokens();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_token_status(&setup.token.address, &ListingStatus::DepositsPaused);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .deposit(&setup.user1, &setup.token.address, &10);
+}
+
+#[test]
+fn check_delisted_token_balance_can_be_withdrawn() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    client
+        .mock_all_auths()
+        .set_token_status(&setup.token.address, &ListingStatus::Delisted);
+
+    let wallet_balance = setup.token.balance(&setup.user1);
+    let id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &10, &None);
+
+    client.mock_all_auths().execute_action(
+        &setup.operator,
+        &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+            id,
+            user: setup.user1.clone(),
+            token: setup.token.address.clone(),
+            amount: 10,
+            execution_status: OperatorWithdrawStatus::Approve,
+        }),
+    );
+
+    assert_eq!(
+        client.balances(&setup.user1, &setup.token.address).balance,
+        0
+    );
+    assert_eq!(setup.token.balance(&setup.user1), wallet_balance + 10);
+}
+
+#[test]
+fn check_list_tokens_and_pairs() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+    let second_pair = String::from_slice(&setup.env, "SPOT_TKN2_FEE");
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_listed_pair()
+        .with_default_pair_status(&ListingStatus::TradingHalted);
+
+    client
+        .mock_all_auths()
+        .set_token_status(&setup.fee_token.address, &ListingStatus::Delisted);
+    client.mock_all_auths().set_pair_status(
+        &second_pair,
+        &setup.token2.address,
+        &setup.fee_token.address,
+        &ListingStatus::Delisted,
+    );
+
+    let tokens = client.list_tokens();
+    assert_eq!(tokens.len(), 3);
+    assert!(tokens.get_unchecked(0) == (setup.token.address.clone(), ListingStatus::Listed));
+    assert!(tokens.get_unchecked(2) == (setup.fee_token.address.clone(), ListingStatus::Delisted));
+
+    assert_eq!(client.list_pairs(&0, &10).len(), 2);
+
+    let pairs = client.list_pairs(&1, &10);
+    assert_eq!(pairs.len(), 1);
+    assert!(
+        pairs.get_unchecked(0)
+            == (
+                second_pair,
+                setup.token2.address.clone(),
+                setup.fee_token.address.clone(),
+                ListingStatus::Delisted
+            )
+    );
+
+    let (_, _, _, default_pair_status) = client.list_pairs(&0, &1).get_unchecked(0);
+    assert!(default_pair_status == ListingStatus::TradingHalted);
+}
+
 #[test]
 fn check_token_listed_delisted() {
     let setup = Setup::new();
@@ -310,7 +618,7 @@ fn check_withdraw_approved() {
         .asset_manager
         .client()
         .mock_all_auths()
-        .request_withdraw(&setup.user1, &setup.token.address, &4);
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
 
     let UserBalances {
         balance,
@@ -327,13 +635,16 @@ fn check_withdraw_approved() {
         .asset_manager
         .client()
         .mock_all_auths()
-        .execute_action(&OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
-            id,
-            user: setup.user1.clone(),
-            token: setup.token.address.clone(),
-            amount: 4,
-            execution_status: OperatorWithdrawStatus::Approve,
-        }));
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Approve,
+            }),
+        );
 
     let UserBalances {
         balance,
@@ -361,7 +672,7 @@ fn check_withdraw_rejected() {
         .asset_manager
         .client()
         .mock_all_auths()
-        .request_withdraw(&setup.user1, &setup.token.address, &4);
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
 
     let UserBalances {
         balance,
@@ -378,13 +689,16 @@ fn check_withdraw_rejected() {
         .asset_manager
         .client()
         .mock_all_auths()
-        .execute_action(&OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
-            id,
-            user: setup.user1.clone(),
-            token: setup.token.address.clone(),
-            amount: 4,
-            execution_status: OperatorWithdrawStatus::Reject,
-        }));
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Reject,
+            }),
+        );
 
     let UserBalances {
         balance,
@@ -398,6 +712,602 @@ fn check_withdraw_rejected() {
     assert_eq!(balance_on_withdraw, 0);
 }
 
+#[test]
+fn check_user_withdraw_requests_listing() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let client = setup.asset_manager.client();
+    let mut ids = vec![&setup.env];
+    for amount in 1..=3 {
+        ids.push_back(client.mock_all_auths().request_withdraw(
+            &setup.user1,
+            &setup.token.address,
+            &amount,
+            &None,
+        ));
+    }
+    client
+        .mock_all_auths()
+        .request_withdraw(&setup.user2, &setup.token2.address, &1, &None);
+
+    client
+        .mock_all_auths()
+        .cancel_withdraw(&setup.user1, &ids.get(0).unwrap());
+
+    assert_eq!(client.user_withdraw_request_count(&setup.user1), 3);
+    assert_eq!(client.user_withdraw_request_count(&setup.user2), 1);
+
+    let first = client.get_withdraw_request(&ids.get(0).unwrap());
+    assert!(first.status == WithdrawStatus::Cancelled);
+
+    let page = client.user_withdraw_requests(&setup.user1, &1, &5);
+    assert_eq!(page.len(), 2);
+    for (i, (id, withdraw_request)) in (1u32..).zip(page.iter()) {
+        assert_eq!(id, ids.get(i).unwrap());
+        assert_eq!(withdraw_request.amount, i128::from(i) + 1);
+        assert!(withdraw_request.status == WithdrawStatus::Requested);
+    }
+
+    assert_eq!(client.user_withdraw_requests(&setup.user1, &3, &5).len(), 0);
+}
+
+#[test]
+fn check_expired_withdraws_swept() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    // requests have to stay force withdrawable for a while before expiring
+    client.mock_all_auths().set_force_withdraw_delay(&50);
+    client.mock_all_auths().set_withdraw_expiry(&100);
+
+    let expiring_id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+    let cancelled_id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &2, &None);
+    client
+        .mock_all_auths()
+        .cancel_withdraw(&setup.user1, &cancelled_id);
+
+    // an unknown id is skipped like the processed ones instead of failing the sweep
+    let ids = vec![&setup.env, expiring_id, cancelled_id, cancelled_id + 100];
+    assert_eq!(client.sweep_expired_withdraws(&ids), 0);
+
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += 100;
+    });
+
+    assert_eq!(client.sweep_expired_withdraws(&ids), 1);
+    assert!(client.get_withdraw_request(&expiring_id).status == WithdrawStatus::Expired);
+    assert!(client.get_withdraw_request(&cancelled_id).status == WithdrawStatus::Cancelled);
+
+    let balances = client.balances(&setup.user1, &setup.token.address);
+    assert_eq!(balances.balance, 10);
+    assert_eq!(balances.balance_on_withdraw, 0);
+}
+
+#[test]
+#[should_panic(expected = "29")]
+fn check_set_withdraw_expiry_fail_on_zero() {
+    let setup = Setup::new();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_withdraw_expiry(&0);
+}
+
+#[test]
+#[should_panic(expected = "29")]
+fn check_set_withdraw_expiry_fail_within_force_withdraw_delay() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    client
+        .mock_all_auths()
+        .set_withdraw_expiry(&client.force_withdraw_delay());
+}
+
+#[test]
+#[should_panic(expected = "29")]
+fn check_set_force_withdraw_delay_fail_beyond_withdraw_expiry() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    client
+        .mock_all_auths()
+        .set_force_withdraw_delay(&client.withdraw_expiry());
+}
+
+#[test]
+fn check_withdraw_to_destination() {
+    let setup = Setup::new();
+    let destination = Address::random(&setup.env);
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(
+            &setup.user1,
+            &setup.token.address,
+            &4,
+            &Some(destination.clone()),
+        );
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Approve,
+            }),
+        );
+
+    assert_eq!(setup.token.balance(&destination), 4);
+    assert_eq!(setup.token.balance(&setup.user1), 0);
+    assert_eq!(
+        setup
+            .asset_manager
+            .client()
+            .balances(&setup.user1, &setup.token.address)
+            .balance_on_withdraw,
+        0
+    );
+}
+
+#[test]
+fn check_withdraw_batch_mixed() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let approved_id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+    let rejected_id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user2, &setup.token2.address, &3, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdrawBatch(vec![
+                &setup.env,
+                ExecutionWithdrawData {
+                    id: approved_id,
+                    user: setup.user1.clone(),
+                    token: setup.token.address.clone(),
+                    amount: 4,
+                    execution_status: OperatorWithdrawStatus::Approve,
+                },
+                ExecutionWithdrawData {
+                    id: rejected_id,
+                    user: setup.user2.clone(),
+                    token: setup.token2.address.clone(),
+                    amount: 3,
+                    execution_status: OperatorWithdrawStatus::Reject,
+                },
+            ]),
+        );
+
+    let user1_balances = setup
+        .asset_manager
+        .client()
+        .balances(&setup.user1, &setup.token.address);
+    assert_eq!(user1_balances.balance, 6);
+    assert_eq!(user1_balances.balance_on_withdraw, 0);
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+
+    let user2_balances = setup
+        .asset_manager
+        .client()
+        .balances(&setup.user2, &setup.token2.address);
+    assert_eq!(user2_balances.balance, 10);
+    assert_eq!(user2_balances.balance_on_withdraw, 0);
+    assert_eq!(setup.token2.balance(&setup.user2), 0);
+}
+
+#[test]
+fn check_withdraw_executed_by_role_operator() {
+    let setup = Setup::new();
+    let withdraw_executor = Address::random(&setup.env);
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .grant_operator_role(&withdraw_executor, &OperatorRole::WithdrawExecutor);
+
+    assert!(setup
+        .asset_manager
+        .client()
+        .has_operator_role(&withdraw_executor, &OperatorRole::WithdrawExecutor));
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_action(
+            &withdraw_executor,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Approve,
+            }),
+        );
+
+    assert_eq!(setup.env.auths()[0].0, withdraw_executor);
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+}
+
+#[test]
+#[should_panic(expected = "19")]
+fn check_execute_action_fail_for_other_role() {
+    let setup = Setup::new();
+    let signature_validator = Address::random(&setup.env);
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .grant_operator_role(&signature_validator, &OperatorRole::SignatureValidator);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_action(
+            &signature_validator,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Approve,
+            }),
+        );
+}
+
+#[test]
+fn check_revoke_operator_role() {
+    let setup = Setup::new();
+    let trade_uploader = Address::random(&setup.env);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .grant_operator_role(&trade_uploader, &OperatorRole::TradeUploader);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .revoke_operator_role(&trade_uploader, &OperatorRole::TradeUploader);
+
+    assert!(!setup
+        .asset_manager
+        .client()
+        .has_operator_role(&trade_uploader, &OperatorRole::TradeUploader));
+}
+
+#[test]
+#[should_panic(expected = "20")]
+fn check_deposit_fail_when_paused() {
+    let setup = Setup::new();
+
+    setup.with_default_listed_tokens();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pause_status(&setup.owner, &PausableOperation::Deposit, &true);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .deposit(&setup.user1, &setup.token.address, &10);
+}
+
+#[test]
+fn check_withdraw_allowed_while_trading_paused() {
+    let setup = Setup::new();
+    let guardian = Address::random(&setup.env);
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_guardian(&guardian);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pause_status(&guardian, &PausableOperation::TradeUpload, &true);
+
+    assert!(setup
+        .asset_manager
+        .client()
+        .is_paused(&PausableOperation::TradeUpload));
+    assert!(!setup
+        .asset_manager
+        .client()
+        .is_paused(&PausableOperation::RequestWithdraw));
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Approve,
+            }),
+        );
+
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+}
+
+#[test]
+fn check_pause_all_operations() {
+    let setup = Setup::new();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pause_status(&setup.owner, &PausableOperation::All, &true);
+
+    assert!(setup
+        .asset_manager
+        .client()
+        .is_paused(&PausableOperation::Deposit));
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pause_status(&setup.owner, &PausableOperation::All, &false);
+
+    assert!(!setup
+        .asset_manager
+        .client()
+        .is_paused(&PausableOperation::Deposit));
+}
+
+#[test]
+#[should_panic(expected = "21")]
+fn check_guardian_unpause_fail() {
+    let setup = Setup::new();
+    let guardian = Address::random(&setup.env);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_guardian(&guardian);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pause_status(&guardian, &PausableOperation::Deposit, &true);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pause_status(&guardian, &PausableOperation::Deposit, &false);
+}
+
+#[test]
+fn check_force_withdraw() {
+    let setup = Setup::new();
+    let force_withdraw_delay = 10;
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_force_withdraw_delay(&force_withdraw_delay);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += force_withdraw_delay;
+    });
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .force_withdraw(&setup.user1, &id);
+
+    let UserBalances {
+        balance,
+        balance_on_withdraw,
+    } = setup
+        .asset_manager
+        .client()
+        .balances(&setup.user1, &setup.token.address);
+
+    assert_eq!(balance, 6);
+    assert_eq!(balance_on_withdraw, 0);
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+}
+
+#[test]
+#[should_panic(expected = "27")]
+fn check_force_withdraw_fail_before_delay() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .force_withdraw(&setup.user1, &id);
+}
+
+#[test]
+fn check_withdraw_cancelled_by_user() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .cancel_withdraw(&setup.user1, &id);
+
+    assert_eq!(setup.env.auths()[0].0, setup.user1);
+
+    let UserBalances {
+        balance,
+        balance_on_withdraw,
+    } = setup
+        .asset_manager
+        .client()
+        .balances(&setup.user1, &setup.token.address);
+
+    assert_eq!(balance, 10);
+    assert_eq!(balance_on_withdraw, 0);
+}
+
+#[test]
+#[should_panic(expected = "14")]
+fn check_cancel_withdraw_fail_after_execution() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+                id,
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                execution_status: OperatorWithdrawStatus::Approve,
+            }),
+        );
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .cancel_withdraw(&setup.user1, &id);
+}
+
 #[test]
 fn check_verify_signature() {
     let setup = Setup::new();
@@ -429,14 +1339,15 @@ fn check_verify_signature() {
         .asset_manager
         .client()
         .mock_all_auths()
-        .execute_action(&OperatorAction::ValidateUserSignature(
-            ValidateUserSignatureData {
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ValidateUserSignature(ValidateUserSignatureData {
                 user: setup.user1,
                 key_id: 1,
                 message: Bytes::from_slice(&setup.env, message),
                 signature: BytesN::from_array(&setup.env, &signature.to_bytes()),
-            },
-        )); // would panic in case the signature is not valid
+            }),
+        ); // would panic in case the signature is not valid
 }
 
 #[test]
@@ -471,12 +1382,13 @@ fn check_verify_signature_failed() {
         .asset_manager
         .client()
         .mock_all_auths()
-        .execute_action(&OperatorAction::ValidateUserSignature(
-            ValidateUserSignatureData {
+        .execute_action(
+            &setup.operator,
+            &OperatorAction::ValidateUserSignature(ValidateUserSignatureData {
                 user: setup.user1,
                 key_id: 1,
                 message: Bytes::from_slice(&setup.env, message),
                 signature: BytesN::from_array(&setup.env, &signature.to_bytes()),
-            },
-        )); // would panic because the signature is made by the other key
+            }),
+        ); // would panic because the signature is made by the other key
 }
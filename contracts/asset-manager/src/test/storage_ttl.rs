@@ -0,0 +1,220 @@
+use soroban_sdk::{testutils::Ledger, vec, BytesN, String};
+
+use crate::{
+    storage_types::{
+        pair_manager::PairStorageInfo, withdraw_request_manager::LegacyWithdrawData, DataKey,
+        KeyManager, ListingStatus, PairManager, TokenManager, UserBalanceManager,
+        WithdrawRequestManager, WithdrawStatus, LISTING_BUMP_AMOUNT, USER_DATA_BUMP_AMOUNT,
+    },
+    test::{Setup, DEFAULT_PAIR},
+    types::{ExecutionWithdrawData, OperatorAction, OperatorWithdrawStatus},
+};
+
+// The host only checks expiration when an entry gets bumped, a zero bump
+// doesn't extend anything and panics if the entry is already archived.
+fn assert_entries_alive(setup: &Setup) {
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        let storage = setup.env.storage();
+        storage.instance().bump(0, 0);
+        storage
+            .persistent()
+            .bump(&TokenManager::new(setup.token.address.clone()), 0, 0);
+        storage.persistent().bump(
+            &PairManager::new(String::from_slice(&setup.env, DEFAULT_PAIR)),
+            0,
+            0,
+        );
+        storage.persistent().bump(&DataKey::TokenRegistry, 0, 0);
+        storage.persistent().bump(&DataKey::PairRegistry, 0, 0);
+    });
+}
+
+#[test]
+fn check_listing_entries_outlive_initial_ttl() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+    let symbol = String::from_slice(&setup.env, DEFAULT_PAIR);
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    for _ in 0..3 {
+        setup.env.ledger().with_mut(|l| {
+            l.sequence_number += LISTING_BUMP_AMOUNT - 1;
+        });
+
+        assert!(client.is_token_listed(&setup.token.address));
+        assert!(client.is_pair_listed(&symbol));
+        assert_eq!(client.list_tokens().len(), 3);
+        assert_eq!(client.list_pairs(&0, &10).len(), 1);
+
+        assert_entries_alive(&setup);
+    }
+
+    client.mock_all_auths().set_pair_status(
+        &symbol,
+        &setup.token.address,
+        &setup.token2.address,
+        &ListingStatus::Delisted,
+    );
+    assert!(!client.is_pair_listed(&symbol));
+}
+
+#[test]
+fn check_instance_listing_moved_to_persistent() {
+    let setup = Setup::new();
+    let pair_manager = PairManager::new(String::from_slice(&setup.env, DEFAULT_PAIR));
+
+    setup.with_default_listed_tokens();
+
+    // simulate a pair listed by a build keeping the listings in the instance storage
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        setup.env.storage().instance().set(
+            &pair_manager,
+            &PairStorageInfo::new(
+                (setup.token.address.clone(), setup.token2.address.clone()),
+                ListingStatus::Listed,
+            ),
+        );
+    });
+    assert!(setup
+        .asset_manager
+        .client()
+        .is_pair_listed(&pair_manager.symbol));
+
+    setup.with_default_pair_status(&ListingStatus::Delisted);
+
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        assert!(!setup.env.storage().instance().has(&pair_manager));
+        assert!(setup.env.storage().persistent().has(&pair_manager));
+    });
+
+    // the pair listed before the registry existed got registered with the update
+    let pairs = setup.asset_manager.client().list_pairs(&0, &10);
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs.get_unchecked(0).0, pair_manager.symbol);
+}
+
+#[test]
+fn check_legacy_withdraw_request_processed() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+    let id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    // simulate a request written by a build preceding the `to` and `created_ledger` fields
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        setup.env.storage().persistent().set(
+            &WithdrawRequestManager::new(id),
+            &LegacyWithdrawData {
+                user: setup.user1.clone(),
+                token: setup.token.address.clone(),
+                amount: 4,
+                status: WithdrawStatus::Requested,
+            },
+        );
+    });
+
+    let withdraw_request = client.get_withdraw_request(&id);
+    assert_eq!(withdraw_request.to, setup.user1);
+    assert_eq!(
+        withdraw_request.created_ledger,
+        setup.env.ledger().sequence()
+    );
+
+    // once migrated the request dates from the migration, so it can't be swept right away
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        setup.env.storage().instance().remove(&DataKey::Version);
+    });
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += 1_000;
+    });
+    let migration_ledger = setup.env.ledger().sequence();
+    client.mock_all_auths().migrate();
+
+    assert_eq!(
+        client.get_withdraw_request(&id).created_ledger,
+        migration_ledger
+    );
+    assert_eq!(client.sweep_expired_withdraws(&vec![&setup.env, id]), 0);
+
+    client.mock_all_auths().execute_action(
+        &setup.operator,
+        &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+            id,
+            user: setup.user1.clone(),
+            token: setup.token.address.clone(),
+            amount: 4,
+            execution_status: OperatorWithdrawStatus::Approve,
+        }),
+    );
+
+    assert!(client.get_withdraw_request(&id).status == WithdrawStatus::Executed);
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+}
+
+#[test]
+fn check_extend_ttl_keeps_user_data_alive() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+    client.mock_all_auths().user_announce_key(
+        &setup.user1,
+        &1,
+        &BytesN::from_array(&setup.env, &[1; 32]),
+    );
+    let withdraw_id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    assert_eq!(client.user_data_extended_until(&setup.user1), 0);
+
+    // past the half of the lifetime the entries got when written
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += USER_DATA_BUMP_AMOUNT / 2;
+    });
+    let extended_ledger = setup.env.ledger().sequence();
+
+    // nobody authorizes the call, an unknown token and key id are skipped
+    client.extend_ttl(
+        &setup.user1,
+        &vec![
+            &setup.env,
+            setup.token.address.clone(),
+            setup.token2.address.clone(),
+        ],
+        &vec![&setup.env, 1, 2],
+    );
+    assert_eq!(
+        client.user_data_extended_until(&setup.user1),
+        extended_ledger + USER_DATA_BUMP_AMOUNT
+    );
+
+    // beyond the lifetime the entries got when written, only alive through `extend_ttl`
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += USER_DATA_BUMP_AMOUNT - 1;
+    });
+
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        let storage = setup.env.storage().persistent();
+        storage.bump(
+            &UserBalanceManager::new(setup.user1.clone(), setup.token.address.clone()),
+            0,
+            0,
+        );
+        storage.bump(&KeyManager::new(setup.user1.clone(), 1), 0, 0);
+        storage.bump(&WithdrawRequestManager::new(withdraw_id), 0, 0);
+    });
+}
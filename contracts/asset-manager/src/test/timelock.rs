@@ -0,0 +1,187 @@
+use soroban_sdk::testutils::Ledger;
+
+use crate::{
+    storage_types::{timelock_manager::MAX_TIMELOCK_DELAY, ListingStatus},
+    test::Setup,
+    types::{AdminAction, TokenStatusData},
+};
+
+const TIMELOCK_DELAY: u32 = 100;
+
+fn advance_ledger_sequence(setup: &Setup, delta: u32) {
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += delta;
+    });
+}
+
+impl Setup<'_> {
+    fn with_timelock_delay(&self, delay: u32) -> &Self {
+        self.asset_manager
+            .client()
+            .mock_all_auths()
+            .set_timelock_delay(&delay);
+        self
+    }
+
+    fn queue_token_listing(&self) -> u64 {
+        self.asset_manager
+            .client()
+            .mock_all_auths()
+            .queue_admin_action(&AdminAction::SetTokenStatus(TokenStatusData {
+                token: self.token.address.clone(),
+                status: ListingStatus::Listed,
+            }))
+    }
+}
+
+#[test]
+fn check_queued_action_executed_after_delay() {
+    let setup = Setup::new();
+    setup.with_timelock_delay(TIMELOCK_DELAY);
+
+    let id = setup.queue_token_listing();
+
+    let queued_action = setup
+        .asset_manager
+        .client()
+        .queued_admin_action(&id)
+        .unwrap();
+    assert_eq!(
+        queued_action.eta,
+        setup.env.ledger().sequence() + TIMELOCK_DELAY
+    );
+
+    advance_ledger_sequence(&setup, TIMELOCK_DELAY);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_admin_action(&id);
+
+    assert!(setup
+        .asset_manager
+        .client()
+        .is_token_listed(&setup.token.address));
+    assert!(setup
+        .asset_manager
+        .client()
+        .queued_admin_action(&id)
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "25")]
+fn check_queued_action_fail_before_delay() {
+    let setup = Setup::new();
+    setup.with_timelock_delay(TIMELOCK_DELAY);
+
+    let id = setup.queue_token_listing();
+
+    advance_ledger_sequence(&setup, TIMELOCK_DELAY - 1);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_admin_action(&id);
+}
+
+#[test]
+#[should_panic(expected = "24")]
+fn check_cancelled_action_fail_to_execute() {
+    let setup = Setup::new();
+    setup.with_timelock_delay(TIMELOCK_DELAY);
+
+    let id = setup.queue_token_listing();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .cancel_admin_action(&id);
+
+    advance_ledger_sequence(&setup, TIMELOCK_DELAY);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_admin_action(&id);
+}
+
+#[test]
+#[should_panic(expected = "23")]
+fn check_direct_admin_call_fail_with_timelock() {
+    let setup = Setup::new();
+    setup.with_timelock_delay(TIMELOCK_DELAY);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_token_status(&setup.token.address, &ListingStatus::Listed);
+}
+
+#[test]
+fn check_timelock_delay_changed_through_queue() {
+    let setup = Setup::new();
+    setup.with_timelock_delay(TIMELOCK_DELAY);
+
+    let id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .queue_admin_action(&AdminAction::SetTimelockDelay(0));
+
+    advance_ledger_sequence(&setup, TIMELOCK_DELAY);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .execute_admin_action(&id);
+
+    assert_eq!(setup.asset_manager.client().timelock_delay(), 0);
+
+    // without the delay admin calls take effect right away again
+    setup.with_default_listed_tokens();
+}
+
+#[test]
+fn check_queued_action_kept_apart_from_withdraw_request() {
+    let setup = Setup::new();
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    let withdraw_id = setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+
+    setup.with_timelock_delay(TIMELOCK_DELAY);
+    let action_id = setup.queue_token_listing();
+    assert_eq!(withdraw_id, action_id);
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .cancel_withdraw(&setup.user1, &withdraw_id);
+
+    assert!(setup
+        .asset_manager
+        .client()
+        .queued_admin_action(&action_id)
+        .is_some());
+}
+
+#[test]
+#[should_panic(expected = "26")]
+fn check_set_timelock_delay_fail_beyond_max() {
+    let setup = Setup::new();
+
+    setup.with_timelock_delay(MAX_TIMELOCK_DELAY + 1);
+}
@@ -0,0 +1,213 @@
+use crate::{
+    storage_types::{
+        token_manager::{DepositLimits, TokenConfig},
+        ListingStatus, TokenManager,
+    },
+    test::Setup,
+    types::{ExecutionWithdrawData, OperatorAction, OperatorWithdrawStatus},
+};
+
+impl Setup<'_> {
+    fn with_deposit_limits(
+        &self,
+        min_deposit: i128,
+        max_user_balance: i128,
+        deposit_cap: i128,
+    ) -> &Self {
+        self.asset_manager
+            .client()
+            .mock_all_auths()
+            .set_token_deposit_limits(
+                &self.token.address,
+                &DepositLimits {
+                    min_deposit,
+                    max_user_balance,
+                    deposit_cap,
+                },
+            );
+        self
+    }
+
+    fn deposit_token(&self, amount: i128) {
+        self.asset_manager.client().mock_all_auths().deposit(
+            &self.user1,
+            &self.token.address,
+            &amount,
+        );
+    }
+}
+
+#[test]
+fn check_token_config_on_listing() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    assert!(client.token_config(&setup.token.address).is_none());
+
+    setup.with_default_listed_tokens();
+
+    let config = client.token_config(&setup.token.address).unwrap();
+    assert!(config.status == ListingStatus::Listed);
+    assert_eq!(config.decimals, setup.token.decimals());
+    assert!(config.limits == DepositLimits::unlimited());
+}
+
+#[test]
+fn check_deposit_within_limits() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(2, 8, 100);
+
+    setup.deposit_token(2);
+    setup.deposit_token(6);
+
+    assert_eq!(
+        setup
+            .asset_manager
+            .client()
+            .balances(&setup.user1, &setup.token.address)
+            .balance,
+        8
+    );
+}
+
+#[test]
+fn check_legacy_listing_status_read_as_config() {
+    let setup = Setup::new();
+
+    // simulate a token listed by a build storing only the listing status
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        setup.env.storage().instance().set(
+            &TokenManager::new(setup.token.address.clone()),
+            &ListingStatus::Listed,
+        );
+    });
+
+    let client = setup.asset_manager.client();
+    assert!(client.is_token_listed(&setup.token.address));
+    assert!(
+        client.token_config(&setup.token.address).unwrap().limits == DepositLimits::unlimited()
+    );
+
+    setup.deposit_token(10);
+
+    // the config got written back, later reads no longer query the token decimals
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        let token_manager = TokenManager::new(setup.token.address.clone());
+        assert!(!setup.env.storage().instance().has(&token_manager));
+        assert!(setup
+            .env
+            .storage()
+            .persistent()
+            .get::<_, TokenConfig>(&token_manager)
+            .is_some());
+    });
+}
+
+#[test]
+fn check_deposit_cap_ignores_direct_transfers() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(2, 8, 6);
+
+    // tokens sent without a deposit don't use up the cap
+    setup
+        .token
+        .mock_all_auths()
+        .transfer(&setup.user1, &setup.asset_manager_id, &4);
+
+    setup.deposit_token(6);
+}
+
+#[test]
+fn check_deposit_cap_freed_by_withdraw() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(2, 8, 6);
+
+    setup.deposit_token(6);
+
+    let id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+    client.mock_all_auths().execute_action(
+        &setup.operator,
+        &OperatorAction::ExecuteWithdraw(ExecutionWithdrawData {
+            id,
+            user: setup.user1.clone(),
+            token: setup.token.address.clone(),
+            amount: 4,
+            execution_status: OperatorWithdrawStatus::Approve,
+        }),
+    );
+
+    setup.deposit_token(4);
+
+    assert_eq!(
+        client.balances(&setup.user1, &setup.token.address).balance,
+        6
+    );
+}
+
+#[test]
+#[should_panic(expected = "30")]
+fn check_set_deposit_limits_fail_on_min_above_max() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(9, 8, 100);
+}
+
+#[test]
+#[should_panic(expected = "6")]
+fn check_set_deposit_limits_fail_for_unlisted_token() {
+    let setup = Setup::new();
+
+    setup.with_deposit_limits(2, 8, 100);
+}
+
+#[test]
+#[should_panic(expected = "31")]
+fn check_deposit_fail_below_minimum() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(2, 8, 100);
+
+    setup.deposit_token(1);
+}
+
+#[test]
+#[should_panic(expected = "32")]
+fn check_deposit_fail_above_user_balance_cap() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(2, 8, 100);
+
+    setup.deposit_token(6);
+    setup.deposit_token(3);
+}
+
+#[test]
+#[should_panic(expected = "33")]
+fn check_deposit_fail_above_deposit_cap() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_deposit_limits(2, 8, 5);
+
+    setup.deposit_token(6);
+}
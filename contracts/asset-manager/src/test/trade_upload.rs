@@ -2,16 +2,163 @@
 
 use ed25519_dalek::{Signer, SigningKey};
 use rand::rngs::OsRng;
-use soroban_sdk::{vec, Bytes, BytesN, String};
+use soroban_sdk::{
+    testutils::{Address as AddressTestTrait, Ledger},
+    vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, String, Vec,
+};
 
 use crate::{
+    storage_types::ListingStatus,
     test::{Setup, DEFAULT_PAIR},
     types::{
-        trade_upload::{TradeUploadData, TradeUploadPair, TradeUploadUnit},
+        trade_upload::{Order, PurchaseSide, TradeUploadData, TradeUploadPair, TradeUploadUnit},
         OperatorAction,
     },
 };
 
+const ORDER_PRICE: i128 = 5;
+const ORDER_QUANTITY: i128 = 1;
+const ORDER_MAX_FEE: i128 = 5;
+
+fn announce_signing_key(setup: &Setup, user: &Address) -> SigningKey {
+    let mut csprng = OsRng;
+    let signing_key: SigningKey = SigningKey::generate(&mut csprng);
+    let verifying_key = signing_key.verifying_key().to_bytes();
+
+    setup.asset_manager.client().user_announce_key(
+        user,
+        &1,
+        &BytesN::from_array(&setup.env, &verifying_key),
+    );
+
+    signing_key
+}
+
+fn default_order(setup: &Setup, account: &Address, side: PurchaseSide) -> Order {
+    Order {
+        contract: setup.asset_manager_id.clone(),
+        network_id: setup.env.ledger().network_id(),
+        account: account.clone(),
+        symbol: String::from_slice(&setup.env, DEFAULT_PAIR),
+        side,
+        price: ORDER_PRICE,
+        quantity: ORDER_QUANTITY,
+        nonce: 0,
+        expiry: u64::MAX,
+        fee_token_asset: setup.fee_token.address.clone(),
+        max_fee_amount: ORDER_MAX_FEE,
+    }
+}
+
+fn sign_bytes(setup: &Setup, signing_key: &SigningKey, bytes: &Bytes) -> BytesN<64> {
+    let mut buffer = [0u8; 1024];
+    let message = &mut buffer[..bytes.len() as usize];
+    bytes.copy_into_slice(message);
+
+    BytesN::from_array(&setup.env, &signing_key.sign(message).to_bytes())
+}
+
+fn sign_order(setup: &Setup, signing_key: &SigningKey, order: &Order) -> (BytesN<64>, Bytes) {
+    let order = order.clone().to_xdr(&setup.env);
+
+    (sign_bytes(setup, signing_key, &order), order)
+}
+
+fn signed_trade_unit(
+    setup: &Setup,
+    signing_key: &SigningKey,
+    account: &Address,
+    side: PurchaseSide,
+    trade_id: u64,
+    quantity: i128,
+    amount: i128,
+) -> TradeUploadUnit {
+    let (order_signature, order) =
+        sign_order(setup, signing_key, &default_order(setup, account, side));
+
+    TradeUploadUnit {
+        trade_id,
+        account: account.clone(),
+        symbol: String::from_slice(&setup.env, DEFAULT_PAIR),
+        side,
+        quantity,
+        price: ORDER_PRICE,
+        amount,
+        fee_amount: 0,
+        fee_token_asset: setup.fee_token.address.clone(),
+        timestamp: 0,
+        order_signature,
+        pub_key_id: 1,
+        order,
+    }
+}
+
+fn trade_pair(
+    setup: &Setup,
+    signing_keys: &(SigningKey, SigningKey),
+    symbol: &str,
+    trade_id: u64,
+) -> TradeUploadPair {
+    let symbol = String::from_slice(&setup.env, symbol);
+    let side_unit = |signing_key, account: &Address, side, trade_id| {
+        let mut order = default_order(setup, account, side);
+        order.symbol = symbol.clone();
+        order.nonce = trade_id;
+
+        let mut unit = signed_trade_unit(setup, signing_key, account, side, trade_id, 1, 5);
+        (unit.order_signature, unit.order) = sign_order(setup, signing_key, &order);
+        unit.symbol = symbol.clone();
+        unit
+    };
+
+    TradeUploadPair {
+        buy_side: side_unit(&signing_keys.1, &setup.user2, PurchaseSide::Buy, trade_id),
+        sell_side: side_unit(
+            &signing_keys.0,
+            &setup.user1,
+            PurchaseSide::Sell,
+            trade_id + 1,
+        ),
+    }
+}
+
+fn upload_trades(setup: &Setup, batch_id: u64, trades: Vec<TradeUploadPair>) {
+    setup.asset_manager.client().execute_action(
+        &setup.operator,
+        &OperatorAction::TradeUpload(TradeUploadData { batch_id, trades }),
+    );
+}
+
+fn default_trade_with_keys(setup: &Setup) -> (TradeUploadPair, (SigningKey, SigningKey)) {
+    let signing_keys = (
+        announce_signing_key(setup, &setup.user1),
+        announce_signing_key(setup, &setup.user2),
+    );
+
+    (
+        trade_pair(setup, &signing_keys, DEFAULT_PAIR, 1),
+        signing_keys,
+    )
+}
+
+fn upload_default_trade(setup: &Setup) {
+    let signing_keys = (
+        announce_signing_key(setup, &setup.user1),
+        announce_signing_key(setup, &setup.user2),
+    );
+
+    upload_trades(
+        setup,
+        1,
+        vec![
+            &setup.env,
+            trade_pair(setup, &signing_keys, DEFAULT_PAIR, 1),
+        ],
+    );
+}
+
 #[test]
 fn operator_trades_upload() {
     let setup = Setup::new();
@@ -41,46 +188,47 @@ fn operator_trades_upload() {
         &BytesN::from_array(&setup.env, &verifying_key2),
     );
 
-    let order_data = r#"
-    {
-        "symbol":"SPOT_TKN1_TKN2",
-        "order_type":"MARKET",
-    }"#;
-
-    let order = Bytes::from_slice(&setup.env, order_data.as_bytes());
+    let (buy_signature, buy_order) = sign_order(
+        &setup,
+        &signing_key2,
+        &default_order(&setup, &setup.user2, PurchaseSide::Buy),
+    );
+    let (sell_signature, sell_order) = sign_order(
+        &setup,
+        &signing_key1,
+        &default_order(&setup, &setup.user1, PurchaseSide::Sell),
+    );
 
     let buy_trade = TradeUploadUnit {
         trade_id: 1,
         account: setup.user2.clone(),
         symbol: String::from_slice(&setup.env, DEFAULT_PAIR),
+        side: PurchaseSide::Buy,
         quantity: 1,
+        price: 5,
         amount: 5,
         fee_amount: 1,
         fee_token_asset: setup.fee_token.address.clone(),
         timestamp: 0,
-        order_signature: BytesN::from_array(
-            &setup.env,
-            &signing_key2.sign(order_data.as_bytes()).to_bytes(),
-        ),
+        order_signature: buy_signature,
         pub_key_id: 1,
-        order: order.clone(),
+        order: buy_order,
     };
 
     let sell_trade = TradeUploadUnit {
         trade_id: 2,
         account: setup.user1.clone(),
         symbol: String::from_slice(&setup.env, DEFAULT_PAIR),
+        side: PurchaseSide::Sell,
         quantity: 1,
+        price: 5,
         amount: 5,
         fee_amount: 2,
         fee_token_asset: setup.fee_token.address.clone(),
         timestamp: 0,
-        order_signature: BytesN::from_array(
-            &setup.env,
-            &signing_key1.sign(order_data.as_bytes()).to_bytes(),
-        ),
+        order_signature: sell_signature,
         pub_key_id: 1,
-        order,
+        order: sell_order,
     };
 
     let trade_upload_pair = TradeUploadPair {
@@ -93,10 +241,10 @@ fn operator_trades_upload() {
         trades: vec![&setup.env, trade_upload_pair],
     };
 
-    setup
-        .asset_manager
-        .client()
-        .execute_action(&OperatorAction::TradeUpload(trade_upload_data));
+    setup.asset_manager.client().execute_action(
+        &setup.operator,
+        &OperatorAction::TradeUpload(trade_upload_data),
+    );
 
     assert_eq!(
         setup
@@ -191,46 +339,47 @@ fn operator_trades_upload_without_fee() {
         &BytesN::from_array(&setup.env, &verifying_key2),
     );
 
-    let order_data = r#"
-    {
-        "symbol":"SPOT_TKN1_TKN2",
-        "order_type":"MARKET",
-    }"#;
-
-    let order = Bytes::from_slice(&setup.env, order_data.as_bytes());
+    let (buy_signature, buy_order) = sign_order(
+        &setup,
+        &signing_key2,
+        &default_order(&setup, &setup.user2, PurchaseSide::Buy),
+    );
+    let (sell_signature, sell_order) = sign_order(
+        &setup,
+        &signing_key1,
+        &default_order(&setup, &setup.user1, PurchaseSide::Sell),
+    );
 
     let buy_trade = TradeUploadUnit {
         trade_id: 1,
         account: setup.user2.clone(),
         symbol: String::from_slice(&setup.env, DEFAULT_PAIR),
+        side: PurchaseSide::Buy,
         quantity: 1,
+        price: 5,
         amount: 5,
         fee_amount: 0,
         fee_token_asset: setup.fee_token.address.clone(),
         timestamp: 0,
-        order_signature: BytesN::from_array(
-            &setup.env,
-            &signing_key2.sign(order_data.as_bytes()).to_bytes(),
-        ),
+        order_signature: buy_signature,
         pub_key_id: 1,
-        order: order.clone(),
+        order: buy_order,
     };
 
     let sell_trade = TradeUploadUnit {
         trade_id: 2,
         account: setup.user1.clone(),
         symbol: String::from_slice(&setup.env, DEFAULT_PAIR),
+        side: PurchaseSide::Sell,
         quantity: 1,
+        price: 5,
         amount: 5,
         fee_amount: 0,
         fee_token_asset: setup.fee_token.address.clone(),
         timestamp: 0,
-        order_signature: BytesN::from_array(
-            &setup.env,
-            &signing_key1.sign(order_data.as_bytes()).to_bytes(),
-        ),
+        order_signature: sell_signature,
         pub_key_id: 1,
-        order,
+        order: sell_order,
     };
 
     let trade_upload_pair = TradeUploadPair {
@@ -243,10 +392,10 @@ fn operator_trades_upload_without_fee() {
         trades: vec![&setup.env, trade_upload_pair],
     };
 
-    setup
-        .asset_manager
-        .client()
-        .execute_action(&OperatorAction::TradeUpload(trade_upload_data));
+    setup.asset_manager.client().execute_action(
+        &setup.operator,
+        &OperatorAction::TradeUpload(trade_upload_data),
+    );
 
     assert_eq!(
         setup
@@ -311,3 +460,850 @@ fn operator_trades_upload_without_fee() {
         0 // no fees where attached to trades
     );
 }
+
+#[test]
+#[should_panic(expected = "7")]
+fn operator_trades_upload_fail_on_balance_on_withdraw() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_key1 = announce_signing_key(&setup, &setup.user1);
+    let signing_key2 = announce_signing_key(&setup, &setup.user2);
+
+    // the whole token balance of the seller is locked by the withdraw request
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, &setup.token.address, &10, &None);
+
+    let trade_upload_pair = TradeUploadPair {
+        buy_side: signed_trade_unit(
+            &setup,
+            &signing_key2,
+            &setup.user2,
+            PurchaseSide::Buy,
+            1,
+            1,
+            5,
+        ),
+        sell_side: signed_trade_unit(
+            &setup,
+            &signing_key1,
+            &setup.user1,
+            PurchaseSide::Sell,
+            2,
+            1,
+            5,
+        ),
+    };
+
+    setup.asset_manager.client().execute_action(
+        &setup.operator,
+        &OperatorAction::TradeUpload(TradeUploadData {
+            batch_id: 1,
+            trades: vec![&setup.env, trade_upload_pair],
+        }),
+    );
+}
+
+#[test]
+fn operator_trades_upload_with_deposits_paused_token() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_token_status(&setup.token.address, &ListingStatus::DepositsPaused);
+
+    upload_default_trade(&setup);
+
+    assert_eq!(
+        setup
+            .asset_manager
+            .client()
+            .balances(&setup.user2, &setup.token.address)
+            .balance,
+        1
+    );
+}
+
+#[test]
+#[should_panic(expected = "35")]
+fn operator_trades_upload_fail_on_halted_pair() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair()
+        .with_default_pair_status(&ListingStatus::TradingHalted);
+
+    upload_default_trade(&setup);
+}
+
+#[test]
+#[should_panic(expected = "36")]
+fn operator_trades_upload_fail_on_delisted_pair_mid_batch() {
+    let setup = Setup::new();
+    let delisted_pair = "SPOT_TKN1_TKN2_OLD";
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    for status in [ListingStatus::Listed, ListingStatus::Delisted] {
+        setup
+            .asset_manager
+            .client()
+            .mock_all_auths()
+            .set_pair_status(
+                &String::from_slice(&setup.env, delisted_pair),
+                &setup.token.address,
+                &setup.token2.address,
+                &status,
+            );
+    }
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+
+    // the first trade settles on a listed pair, the whole batch reverts on the second one
+    upload_trades(
+        &setup,
+        1,
+        vec![
+            &setup.env,
+            trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 1),
+            trade_pair(&setup, &signing_keys, delisted_pair, 3),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "36")]
+fn operator_trades_upload_fail_on_unknown_pair() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5);
+
+    upload_default_trade(&setup);
+}
+
+#[test]
+#[should_panic(expected = "37")]
+fn operator_trades_upload_fail_on_token_delisted_between_batches() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+
+    upload_trades(
+        &setup,
+        1,
+        vec![
+            &setup.env,
+            trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 1),
+        ],
+    );
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_token_status(&setup.token2.address, &ListingStatus::Delisted);
+
+    upload_trades(
+        &setup,
+        2,
+        vec![
+            &setup.env,
+            trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 3),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "38")]
+fn operator_trades_upload_fail_on_undecodable_order() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, signing_keys) = default_trade_with_keys(&setup);
+
+    // a validly signed payload which isn't an order
+    let payload = String::from_slice(&setup.env, DEFAULT_PAIR).to_xdr(&setup.env);
+    trade.buy_side.order_signature = sign_bytes(&setup, &signing_keys.1, &payload);
+    trade.buy_side.order = payload;
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "38")]
+fn operator_trades_upload_fail_on_order_for_other_contract() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, signing_keys) = default_trade_with_keys(&setup);
+
+    // signed for another deployment the buyer announced the same key to
+    let mut order = default_order(&setup, &setup.user2, PurchaseSide::Buy);
+    order.contract = Address::random(&setup.env);
+    (trade.buy_side.order_signature, trade.buy_side.order) =
+        sign_order(&setup, &signing_keys.1, &order);
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "39")]
+fn operator_trades_upload_fail_on_order_side_mismatch() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, signing_keys) = default_trade_with_keys(&setup);
+
+    // the buyer signed a sell order
+    (trade.buy_side.order_signature, trade.buy_side.order) = sign_order(
+        &setup,
+        &signing_keys.1,
+        &default_order(&setup, &setup.user2, PurchaseSide::Sell),
+    );
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "43")]
+fn operator_trades_upload_fail_on_quantity_above_order() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    for side in [&mut trade.buy_side, &mut trade.sell_side] {
+        side.quantity = ORDER_QUANTITY + 1;
+        side.amount = (ORDER_QUANTITY + 1) * ORDER_PRICE;
+    }
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "46")]
+fn operator_trades_upload_fail_on_price_above_buy_order() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    for side in [&mut trade.buy_side, &mut trade.sell_side] {
+        side.price = ORDER_PRICE + 1;
+        side.amount = ORDER_QUANTITY * (ORDER_PRICE + 1);
+    }
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "46")]
+fn operator_trades_upload_fail_on_price_below_sell_order() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    for side in [&mut trade.buy_side, &mut trade.sell_side] {
+        side.price = ORDER_PRICE - 1;
+        side.amount = ORDER_QUANTITY * (ORDER_PRICE - 1);
+    }
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "44")]
+fn operator_trades_upload_fail_on_leg_side_mismatch() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    trade.sell_side.side = PurchaseSide::Buy;
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "45")]
+fn operator_trades_upload_fail_on_amount_not_matching_price() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+    // limits wide enough for the amount, which doesn't match the execution price
+    let mut buy_order = default_order(&setup, &setup.user2, PurchaseSide::Buy);
+    buy_order.price = ORDER_PRICE + 2;
+    let mut sell_order = default_order(&setup, &setup.user1, PurchaseSide::Sell);
+    sell_order.price = ORDER_PRICE - 2;
+
+    let mut trade = trade_filling_orders(
+        &setup,
+        &signing_keys,
+        &(
+            sign_order(&setup, &signing_keys.1, &buy_order),
+            sign_order(&setup, &signing_keys.0, &sell_order),
+        ),
+        1,
+    );
+    for side in [&mut trade.buy_side, &mut trade.sell_side] {
+        side.amount = ORDER_QUANTITY * ORDER_PRICE + 1;
+    }
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "40")]
+fn operator_trades_upload_fail_on_expired_order() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, signing_keys) = default_trade_with_keys(&setup);
+
+    let mut order = default_order(&setup, &setup.user1, PurchaseSide::Sell);
+    order.expiry = 100;
+    (trade.sell_side.order_signature, trade.sell_side.order) =
+        sign_order(&setup, &signing_keys.0, &order);
+
+    setup.env.ledger().with_mut(|l| l.timestamp = 101);
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "41")]
+fn operator_trades_upload_fail_on_fee_above_order_limit() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    trade.sell_side.fee_amount = ORDER_MAX_FEE + 1;
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "42")]
+fn operator_trades_upload_fail_on_trade_replayed_in_next_batch() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+
+    for batch_id in 1..=2 {
+        upload_trades(
+            &setup,
+            batch_id,
+            vec![
+                &setup.env,
+                trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 1),
+            ],
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "42")]
+fn operator_trades_upload_fail_on_trade_replayed_in_same_batch() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+
+    upload_trades(
+        &setup,
+        1,
+        vec![
+            &setup.env,
+            trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 1),
+            trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 1),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "43")]
+fn operator_trades_upload_fail_on_order_replayed_with_new_trade_ids() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (trade, signing_keys) = default_trade_with_keys(&setup);
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+
+    // the same signed orders, settled again under fresh trade ids
+    let mut replayed = trade_pair(&setup, &signing_keys, DEFAULT_PAIR, 1);
+    replayed.buy_side.trade_id = 3;
+    replayed.sell_side.trade_id = 4;
+
+    upload_trades(&setup, 2, vec![&setup.env, replayed]);
+}
+
+type SignedOrder = (BytesN<64>, Bytes);
+
+fn signed_orders_with_quantities(
+    setup: &Setup,
+    signing_keys: &(SigningKey, SigningKey),
+    buy_quantity: i128,
+    sell_quantity: i128,
+) -> (SignedOrder, SignedOrder) {
+    let mut buy_order = default_order(setup, &setup.user2, PurchaseSide::Buy);
+    buy_order.quantity = buy_quantity;
+    let mut sell_order = default_order(setup, &setup.user1, PurchaseSide::Sell);
+    sell_order.quantity = sell_quantity;
+
+    (
+        sign_order(setup, &signing_keys.1, &buy_order),
+        sign_order(setup, &signing_keys.0, &sell_order),
+    )
+}
+
+fn trade_filling_orders(
+    setup: &Setup,
+    signing_keys: &(SigningKey, SigningKey),
+    orders: &(SignedOrder, SignedOrder),
+    trade_id: u64,
+) -> TradeUploadPair {
+    let mut trade = trade_pair(setup, signing_keys, DEFAULT_PAIR, trade_id);
+    (trade.buy_side.order_signature, trade.buy_side.order) = orders.0.clone();
+    (trade.sell_side.order_signature, trade.sell_side.order) = orders.1.clone();
+
+    trade
+}
+
+#[test]
+fn operator_trades_upload_partially_fills_orders() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+    let orders = signed_orders_with_quantities(&setup, &signing_keys, 3, 2);
+    let buy_order_hash = setup.env.crypto().sha256(&orders.0 .1);
+    let sell_order_hash = setup.env.crypto().sha256(&orders.1 .1);
+
+    assert!(client.order_fill_status(&buy_order_hash).is_none());
+
+    // each trade settles a single unit of both orders
+    for batch_id in 1..=2 {
+        upload_trades(
+            &setup,
+            batch_id,
+            vec![
+                &setup.env,
+                trade_filling_orders(&setup, &signing_keys, &orders, batch_id * 2),
+            ],
+        );
+    }
+
+    let buy_fill = client.order_fill_status(&buy_order_hash).unwrap();
+    assert_eq!(buy_fill.quantity, 3);
+    assert_eq!(buy_fill.filled_quantity, 2);
+    assert_eq!(buy_fill.remaining_quantity(), 1);
+
+    let sell_fill = client.order_fill_status(&sell_order_hash).unwrap();
+    assert_eq!(sell_fill.filled_quantity, 2);
+    assert_eq!(sell_fill.remaining_quantity(), 0);
+
+    assert_eq!(
+        client.balances(&setup.user2, &setup.token.address).balance,
+        2
+    );
+}
+
+#[test]
+#[should_panic(expected = "43")]
+fn operator_trades_upload_fail_on_fill_above_remaining_quantity() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+    let orders = signed_orders_with_quantities(&setup, &signing_keys, 2, 2);
+
+    upload_trades(
+        &setup,
+        1,
+        vec![
+            &setup.env,
+            trade_filling_orders(&setup, &signing_keys, &orders, 1),
+        ],
+    );
+
+    // a single unit is left on both orders
+    let mut trade = trade_filling_orders(&setup, &signing_keys, &orders, 3);
+    for side in [&mut trade.buy_side, &mut trade.sell_side] {
+        side.quantity = 2;
+        side.amount = 2 * ORDER_PRICE;
+    }
+
+    upload_trades(&setup, 2, vec![&setup.env, trade]);
+}
+
+/// 3 units at 2.50 on a pair with 2 price decimals, signed with a 2.60 buy
+/// and a 2.30 sell limit so the settled amount allows 6.9 up to 7.8.
+fn price_decimals_trade(setup: &Setup, amount: i128) -> TradeUploadPair {
+    let symbol = String::from_slice(&setup.env, DEFAULT_PAIR);
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pair_price_decimals(&symbol, &2);
+
+    let signing_keys = (
+        announce_signing_key(setup, &setup.user1),
+        announce_signing_key(setup, &setup.user2),
+    );
+    let mut buy_order = default_order(setup, &setup.user2, PurchaseSide::Buy);
+    buy_order.quantity = 3;
+    buy_order.price = 260;
+    let mut sell_order = default_order(setup, &setup.user1, PurchaseSide::Sell);
+    sell_order.quantity = 3;
+    sell_order.price = 230;
+
+    let mut trade = trade_filling_orders(
+        setup,
+        &signing_keys,
+        &(
+            sign_order(setup, &signing_keys.1, &buy_order),
+            sign_order(setup, &signing_keys.0, &sell_order),
+        ),
+        1,
+    );
+    for side in [&mut trade.buy_side, &mut trade.sell_side] {
+        side.quantity = 3;
+        side.price = 250;
+        side.amount = amount;
+    }
+
+    trade
+}
+
+#[test]
+fn operator_trades_upload_with_pair_price_decimals() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    // the 7.5 quote amount is rounded down to a whole quote unit
+    upload_trades(&setup, 1, vec![&setup.env, price_decimals_trade(&setup, 7)]);
+
+    assert_eq!(
+        client.pair_price_decimals(&String::from_slice(&setup.env, DEFAULT_PAIR)),
+        2
+    );
+    assert_eq!(
+        client.balances(&setup.user2, &setup.token.address).balance,
+        3
+    );
+    assert_eq!(
+        client.balances(&setup.user1, &setup.token2.address).balance,
+        7
+    );
+}
+
+#[test]
+#[should_panic(expected = "46")]
+fn operator_trades_upload_fail_on_amount_rounded_above_buy_limit() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    // rounding the 7.5 quote amount up charges the buyer more than the signed 7.8
+    upload_trades(&setup, 1, vec![&setup.env, price_decimals_trade(&setup, 8)]);
+}
+
+#[test]
+#[should_panic(expected = "47")]
+fn set_pair_price_decimals_fail_above_max() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_listed_pair();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pair_price_decimals(&String::from_slice(&setup.env, DEFAULT_PAIR), &19);
+}
+
+#[test]
+#[should_panic(expected = "36")]
+fn set_pair_price_decimals_fail_for_unknown_pair() {
+    let setup = Setup::new();
+
+    setup
+        .asset_manager
+        .client()
+        .mock_all_auths()
+        .set_pair_price_decimals(&String::from_slice(&setup.env, DEFAULT_PAIR), &2);
+}
+
+#[test]
+#[should_panic(expected = "48")]
+fn operator_trades_upload_fail_on_legs_amount_mismatch() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    // the buyer would pay less than what the seller receives
+    trade.buy_side.amount = ORDER_QUANTITY * ORDER_PRICE - 1;
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "50")]
+fn operator_trades_upload_fail_on_duplicate_leg_trade_id() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    trade.sell_side.trade_id = trade.buy_side.trade_id;
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
+
+fn self_trade(setup: &Setup) -> TradeUploadPair {
+    let signing_keys = (
+        announce_signing_key(setup, &setup.user1),
+        announce_signing_key(setup, &setup.user2),
+    );
+    let orders = (
+        sign_order(
+            setup,
+            &signing_keys.1,
+            &default_order(setup, &setup.user2, PurchaseSide::Buy),
+        ),
+        sign_order(
+            setup,
+            &signing_keys.1,
+            &default_order(setup, &setup.user2, PurchaseSide::Sell),
+        ),
+    );
+
+    let mut trade = trade_filling_orders(setup, &signing_keys, &orders, 1);
+    trade.sell_side.account = setup.user2.clone();
+    trade
+}
+
+#[test]
+#[should_panic(expected = "49")]
+fn operator_trades_upload_fail_on_self_trade() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    assert!(setup.asset_manager.client().self_trade_prevention());
+
+    upload_trades(&setup, 1, vec![&setup.env, self_trade(&setup)]);
+}
+
+#[test]
+fn operator_trades_upload_self_trade_when_allowed() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    client.mock_all_auths().set_self_trade_prevention(&false);
+    assert!(!client.self_trade_prevention());
+
+    upload_trades(&setup, 1, vec![&setup.env, self_trade(&setup)]);
+
+    assert_eq!(
+        client.balances(&setup.user2, &setup.token.address).balance,
+        0
+    );
+    assert_eq!(
+        client.balances(&setup.user2, &setup.token2.address).balance,
+        10
+    );
+}
+
+#[test]
+#[should_panic(expected = "41")]
+fn operator_trades_upload_fail_on_fills_above_order_fee_limit() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let signing_keys = (
+        announce_signing_key(&setup, &setup.user1),
+        announce_signing_key(&setup, &setup.user2),
+    );
+    let orders = signed_orders_with_quantities(&setup, &signing_keys, 2, 2);
+    let sell_order_hash = setup.env.crypto().sha256(&orders.1 .1);
+
+    // each fill stays under the signed limit, both together don't
+    let fill_fee_amount = ORDER_MAX_FEE / 2 + 1;
+    let mut trade = trade_filling_orders(&setup, &signing_keys, &orders, 1);
+    trade.sell_side.fee_amount = fill_fee_amount;
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+
+    assert_eq!(
+        client
+            .order_fill_status(&sell_order_hash)
+            .unwrap()
+            .filled_fee_amount,
+        fill_fee_amount
+    );
+
+    let mut trade = trade_filling_orders(&setup, &signing_keys, &orders, 3);
+    trade.sell_side.fee_amount = fill_fee_amount;
+    upload_trades(&setup, 2, vec![&setup.env, trade]);
+}
+
+#[test]
+#[should_panic(expected = "41")]
+fn operator_trades_upload_fail_on_negative_fee() {
+    let setup = Setup::new();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_default_listed_pair();
+
+    let (mut trade, _) = default_trade_with_keys(&setup);
+
+    trade.buy_side.fee_amount = -1;
+
+    upload_trades(&setup, 1, vec![&setup.env, trade]);
+}
@@ -0,0 +1,25 @@
+use crate::{storage_types::DataKey, test::Setup, CONTRACT_VERSION};
+
+#[test]
+fn check_migrate_after_version_bump() {
+    let setup = Setup::new();
+
+    // simulate storage written by a build preceding the version tracking
+    setup.env.as_contract(&setup.asset_manager_id, || {
+        setup.env.storage().instance().remove(&DataKey::Version);
+    });
+    assert_eq!(setup.asset_manager.client().version(), 0);
+
+    setup.asset_manager.client().mock_all_auths().migrate();
+
+    assert_eq!(setup.env.auths()[0].0, setup.owner);
+    assert_eq!(setup.asset_manager.client().version(), CONTRACT_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "22")]
+fn check_migrate_fail_without_version_bump() {
+    let setup = Setup::new();
+
+    setup.asset_manager.client().mock_all_auths().migrate();
+}
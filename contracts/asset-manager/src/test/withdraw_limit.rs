@@ -0,0 +1,245 @@
+use soroban_sdk::{testutils::Ledger, vec, Address};
+
+use crate::{
+    storage_types::{withdraw_limit_manager::WithdrawLimit, WithdrawStatus},
+    test::Setup,
+    types::{ExecutionWithdrawData, OperatorAction, OperatorWithdrawStatus},
+};
+
+const LIMIT_WINDOW: u32 = 100;
+// usage expires one tenth of the window at a time
+const LIMIT_BUCKET: u32 = LIMIT_WINDOW / 10;
+
+impl Setup<'_> {
+    fn with_withdraw_limit(&self, token: &Address, user_cap: i128, global_cap: i128) -> &Self {
+        self.asset_manager
+            .client()
+            .mock_all_auths()
+            .set_withdraw_limit(
+                token,
+                &WithdrawLimit {
+                    window: LIMIT_WINDOW,
+                    user_cap,
+                    global_cap,
+                },
+            );
+        self
+    }
+
+    fn approve_withdraw_data(
+        id: u64,
+        user: &Address,
+        token: &Address,
+        amount: i128,
+    ) -> ExecutionWithdrawData {
+        ExecutionWithdrawData {
+            id,
+            user: user.clone(),
+            token: token.clone(),
+            amount,
+            execution_status: OperatorWithdrawStatus::Approve,
+        }
+    }
+
+    fn withdraw_status(&self, id: u64) -> WithdrawStatus {
+        self.asset_manager.client().get_withdraw_request(&id).status
+    }
+}
+
+#[test]
+fn check_withdraw_over_user_cap_queued_until_window_passes() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_withdraw_limit(&setup.token.address, 5, 100);
+
+    let first_id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &4, &None);
+    let second_id =
+        client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, &setup.token.address, &3, &None);
+
+    for id in [first_id, second_id, second_id] {
+        let amount = if id == first_id { 4 } else { 3 };
+        client.mock_all_auths().execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(Setup::approve_withdraw_data(
+                id,
+                &setup.user1,
+                &setup.token.address,
+                amount,
+            )),
+        );
+    }
+
+    assert!(setup.withdraw_status(first_id) == WithdrawStatus::Executed);
+    assert!(setup.withdraw_status(second_id) == WithdrawStatus::Queued);
+    assert_eq!(setup.token.balance(&setup.user1), 4);
+    assert_eq!(
+        client
+            .balances(&setup.user1, &setup.token.address)
+            .balance_on_withdraw,
+        3
+    );
+
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += LIMIT_WINDOW + LIMIT_BUCKET;
+    });
+
+    client.mock_all_auths().execute_action(
+        &setup.operator,
+        &OperatorAction::ExecuteWithdraw(Setup::approve_withdraw_data(
+            second_id,
+            &setup.user1,
+            &setup.token.address,
+            3,
+        )),
+    );
+
+    assert!(setup.withdraw_status(second_id) == WithdrawStatus::Executed);
+    assert_eq!(setup.token.balance(&setup.user1), 7);
+}
+
+#[test]
+fn check_withdraw_limit_window_slides() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+    let token = &setup.token.address;
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_withdraw_limit(token, 5, 100);
+
+    let approve = |amount: i128| {
+        let id = client
+            .mock_all_auths()
+            .request_withdraw(&setup.user1, token, &amount, &None);
+        client.mock_all_auths().execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(Setup::approve_withdraw_data(
+                id,
+                &setup.user1,
+                token,
+                amount,
+            )),
+        );
+        setup.withdraw_status(id)
+    };
+
+    assert!(approve(3) == WithdrawStatus::Executed);
+
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += LIMIT_WINDOW / 2;
+    });
+    assert!(approve(2) == WithdrawStatus::Executed);
+
+    // the first withdraw left the window, the second one still counts against the cap
+    setup.env.ledger().with_mut(|l| {
+        l.sequence_number += LIMIT_WINDOW / 2 + LIMIT_BUCKET;
+    });
+    assert!(approve(4) == WithdrawStatus::Queued);
+    assert!(approve(1) == WithdrawStatus::Executed);
+}
+
+#[test]
+fn check_withdraw_over_cap_queued_until_cap_raised() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+    let token = &setup.token.address;
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_withdraw_limit(token, 5, 100);
+
+    let id = client
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, token, &6, &None);
+    let approve = || {
+        client.mock_all_auths().execute_action(
+            &setup.operator,
+            &OperatorAction::ExecuteWithdraw(Setup::approve_withdraw_data(
+                id,
+                &setup.user1,
+                token,
+                6,
+            )),
+        );
+    };
+
+    // no window lets the request pass, it waits for the owner instead of bouncing back
+    approve();
+    assert!(setup.withdraw_status(id) == WithdrawStatus::Queued);
+
+    setup.with_withdraw_limit(token, 6, 100);
+    approve();
+
+    assert!(setup.withdraw_status(id) == WithdrawStatus::Executed);
+    assert_eq!(setup.token.balance(&setup.user1), 6);
+}
+
+#[test]
+fn check_withdraw_over_global_cap_queued() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+    let fee_token = &setup.fee_token.address;
+
+    setup
+        .with_default_listed_tokens()
+        .with_default_deposit(10, 5)
+        .with_withdraw_limit(fee_token, 5, 6);
+
+    let user1_id = client
+        .mock_all_auths()
+        .request_withdraw(&setup.user1, fee_token, &4, &None);
+    let user2_id = client
+        .mock_all_auths()
+        .request_withdraw(&setup.user2, fee_token, &4, &None);
+
+    client.mock_all_auths().execute_action(
+        &setup.operator,
+        &OperatorAction::ExecuteWithdrawBatch(vec![
+            &setup.env,
+            Setup::approve_withdraw_data(user1_id, &setup.user1, fee_token, 4),
+            Setup::approve_withdraw_data(user2_id, &setup.user2, fee_token, 4),
+        ]),
+    );
+
+    assert!(setup.withdraw_status(user1_id) == WithdrawStatus::Executed);
+    assert!(setup.withdraw_status(user2_id) == WithdrawStatus::Queued);
+
+    // a queued request can still be cancelled by its user
+    client
+        .mock_all_auths()
+        .cancel_withdraw(&setup.user2, &user2_id);
+
+    assert_eq!(client.balances(&setup.user2, fee_token).balance, 5);
+}
+
+#[test]
+fn check_withdraw_limit_removed() {
+    let setup = Setup::new();
+    let client = setup.asset_manager.client();
+
+    setup.with_withdraw_limit(&setup.token.address, 5, 6);
+    client
+        .mock_all_auths()
+        .remove_withdraw_limit(&setup.token.address);
+
+    assert!(client.withdraw_limit(&setup.token.address).is_none());
+}
+
+#[test]
+#[should_panic(expected = "28")]
+fn check_set_withdraw_limit_fail_on_zero_cap() {
+    let setup = Setup::new();
+
+    setup.with_withdraw_limit(&setup.token.address, 0, 6);
+}
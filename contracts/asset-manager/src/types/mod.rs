@@ -1,5 +1,11 @@
-use crate::types::trade_upload::TradeUploadData;
-use soroban_sdk::{contracttype, Address, Bytes, BytesN};
+use crate::{
+    storage_types::{
+        token_manager::DepositLimits, withdraw_limit_manager::WithdrawLimit, ListingStatus,
+        OperatorRole,
+    },
+    types::trade_upload::TradeUploadData,
+};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Vec};
 pub(crate) mod trade_upload;
 
 #[contracttype]
@@ -23,11 +29,92 @@ pub struct ExecutionWithdrawData {
 pub enum OperatorAction {
     ValidateUserSignature(ValidateUserSignatureData),
     ExecuteWithdraw(ExecutionWithdrawData),
+    ExecuteWithdrawBatch(Vec<ExecutionWithdrawData>),
     TradeUpload(TradeUploadData),
 }
 
+impl OperatorAction {
+    pub fn required_role(&self) -> OperatorRole {
+        match self {
+            OperatorAction::ValidateUserSignature(_) => OperatorRole::SignatureValidator,
+            OperatorAction::ExecuteWithdraw(_) | OperatorAction::ExecuteWithdrawBatch(_) => {
+                OperatorRole::WithdrawExecutor
+            }
+            OperatorAction::TradeUpload(_) => OperatorRole::TradeUploader,
+        }
+    }
+}
+
 #[contracttype]
 pub enum OperatorWithdrawStatus {
     Approve,
     Reject,
 }
+
+#[contracttype]
+#[derive(Clone)]
+pub struct TokenStatusData {
+    pub token: Address,
+    pub status: ListingStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct TokenDepositLimitsData {
+    pub token: Address,
+    pub limits: DepositLimits,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PairStatusData {
+    pub symbol: String,
+    pub token1: Address,
+    pub token2: Address,
+    pub status: ListingStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PairPriceDecimalsData {
+    pub symbol: String,
+    pub price_decimals: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct OperatorRoleData {
+    pub operator: Address,
+    pub role: OperatorRole,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct WithdrawLimitData {
+    pub token: Address,
+    pub limit: WithdrawLimit,
+}
+
+/// Owner changes which can be queued in the timelock.
+/// Ownership transfer and pausing are kept out of it on purpose,
+/// both have to take effect without waiting.
+#[contracttype]
+#[derive(Clone)]
+pub enum AdminAction {
+    SetTokenStatus(TokenStatusData),
+    SetTokenDepositLimits(TokenDepositLimitsData),
+    SetPairStatus(PairStatusData),
+    SetPairPriceDecimals(PairPriceDecimalsData),
+    SetOperatorManager(Address),
+    SetFeeCollector(Address),
+    GrantOperatorRole(OperatorRoleData),
+    RevokeOperatorRole(OperatorRoleData),
+    SetGuardian(Address),
+    SetTimelockDelay(u32),
+    SetForceWithdrawDelay(u32),
+    SetWithdrawExpiry(u32),
+    SetSelfTradePrevention(bool),
+    SetWithdrawLimit(WithdrawLimitData),
+    RemoveWithdrawLimit(Address),
+    Upgrade(BytesN<32>),
+}
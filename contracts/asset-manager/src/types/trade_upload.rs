@@ -1,15 +1,43 @@
-use soroban_sdk::{assert_with_error, contracttype, Address, Bytes, BytesN, Env, String, Vec};
+use soroban_sdk::{
+    assert_with_error, contracttype, panic_with_error, xdr::FromXdr, Address, Bytes, BytesN, Env,
+    String, Vec,
+};
 
 use crate::error::Error;
-use crate::get_fee_collector;
-use crate::storage_types::{self, PairManager, UserBalanceManager};
+use crate::storage_types::{
+    self, OrderFillManager, PairManager, SettledTradeManager, TokenManager, UserBalanceManager,
+};
+use crate::{get_fee_collector, get_self_trade_prevention};
 
-#[derive(PartialEq)]
-enum PurchaseSide {
+#[contracttype]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PurchaseSide {
     Buy,
     Sell,
 }
 
+/// The order as signed by the user, the signature covers its XDR serialization
+/// which is uploaded as is in `TradeUploadUnit::order`.
+#[contracttype]
+#[derive(Clone)]
+pub struct Order {
+    // the contract and network the order settles on, so the signature can't be
+    // replayed on another deployment the user announced the same key to
+    pub contract: Address,
+    pub network_id: BytesN<32>,
+    pub account: Address,
+    pub symbol: String,
+    pub side: PurchaseSide,
+    // limit price, with the pair price decimals
+    pub price: i128,
+    pub quantity: i128,
+    pub nonce: u64,
+    // ledger timestamp after which the order can't be settled anymore
+    pub expiry: u64,
+    pub fee_token_asset: Address,
+    pub max_fee_amount: i128,
+}
+
 #[contracttype]
 pub struct TradeUploadPair {
     pub buy_side: TradeUploadUnit,
@@ -21,15 +49,17 @@ pub struct TradeUploadUnit {
     pub trade_id: u64,
     pub account: Address,
     pub symbol: String,
-    // pub side: PurchaseSide,
+    pub side: PurchaseSide,
     pub quantity: i128,
-    // pub price: i128,
+    // execution price, with the pair price decimals
+    pub price: i128,
     pub amount: i128,
     pub fee_amount: i128,
     pub fee_token_asset: Address,
     pub timestamp: u64,
     pub order_signature: BytesN<64>,
     pub pub_key_id: u32,
+    // XDR serialized `Order`
     pub order: Bytes,
 }
 
@@ -40,12 +70,35 @@ pub struct TradeUploadData {
 }
 
 impl TradeUploadPair {
+    /// Both legs settle the same trade, so they have to move the same base quantity
+    /// for the same quote amount, otherwise the swap would create or destroy value.
+    pub fn assert_legs_match(&self, e: &Env) {
+        assert_with_error!(
+            e,
+            self.buy_side.trade_id != self.sell_side.trade_id,
+            Error::ErrDuplicateTradeId
+        );
+        assert_with_error!(
+            e,
+            self.buy_side.quantity == self.sell_side.quantity
+                && self.buy_side.amount == self.sell_side.amount,
+            Error::ErrTradeLegsMismatch
+        );
+        assert_with_error!(
+            e,
+            self.buy_side.account != self.sell_side.account || !get_self_trade_prevention(e),
+            Error::ErrSelfTrade
+        );
+    }
+
     pub fn verify_signatures(&self, e: &Env) {
-        Self::verify_signature(e, &self.buy_side);
-        Self::verify_signature(e, &self.sell_side);
+        Self::verify_signature(e, &self.buy_side, PurchaseSide::Buy);
+        Self::verify_signature(e, &self.sell_side, PurchaseSide::Sell);
     }
 
-    fn verify_signature(e: &Env, trade_upload: &TradeUploadUnit) {
+    fn verify_signature(e: &Env, trade_upload: &TradeUploadUnit, side: PurchaseSide) {
+        assert_with_error!(e, trade_upload.side == side, Error::ErrTradeSideMismatch);
+
         let key_manager =
             storage_types::KeyManager::new(trade_upload.account.clone(), trade_upload.pub_key_id);
 
@@ -56,6 +109,19 @@ impl TradeUploadPair {
             &trade_upload.order,
             &trade_upload.order_signature,
         );
+
+        let order = Order::from_xdr(e, &trade_upload.order)
+            .unwrap_or_else(|_| panic_with_error!(e, Error::ErrInvalidOrder));
+        let price_decimals = PairManager::new(trade_upload.symbol.clone()).read_price_decimals(e);
+        order.assert_authorizes(e, trade_upload, price_decimals);
+
+        // consumed once verified so neither the trade nor the order can be replayed,
+        // whether in the same batch or in a later one
+        SettledTradeManager::new(trade_upload.trade_id).mark_settled(e);
+        let order_fill_manager = OrderFillManager::new(e.crypto().sha256(&trade_upload.order));
+        let order_fill =
+            order_fill_manager.fill(e, &order, trade_upload.quantity, trade_upload.fee_amount);
+        order_fill_manager.emit_order_fill(e, order_fill);
     }
 
     pub fn execute_pair_swap(&self, e: &Env) {
@@ -66,7 +132,14 @@ impl TradeUploadPair {
         );
         let pair_manager = PairManager::new(self.buy_side.symbol.clone());
 
-        let pair = pair_manager.get_pair(e);
+        let pair = pair_manager.get_tradable_pair(e);
+        TokenManager::new(pair.0.clone()).assert_trading_allowed(e);
+        TokenManager::new(pair.1.clone()).assert_trading_allowed(e);
+
+        let price_decimals = pair_manager.read_price_decimals(e);
+        self.buy_side.assert_amount_matches_price(e, price_decimals);
+        self.sell_side
+            .assert_amount_matches_price(e, price_decimals);
 
         Self::execute_trade(e, &self.buy_side, &pair, PurchaseSide::Buy);
         Self::withdraw_fee(e, &self.buy_side);
@@ -142,3 +215,65 @@ impl TradeUploadPair {
         });
     }
 }
+
+impl Order {
+    fn assert_authorizes(&self, e: &Env, trade: &TradeUploadUnit, price_decimals: u32) {
+        assert_with_error!(
+            e,
+            self.contract == e.current_contract_address()
+                && self.network_id == e.ledger().network_id(),
+            Error::ErrInvalidOrder
+        );
+        assert_with_error!(
+            e,
+            self.account == trade.account
+                && self.symbol == trade.symbol
+                && self.side == trade.side
+                && self.fee_token_asset == trade.fee_token_asset,
+            Error::ErrOrderTradeMismatch
+        );
+        // checked against the remaining quantity once the order fills are known
+        assert_with_error!(e, trade.quantity > 0, Error::ErrOrderTradeMismatch);
+        // a negative fee would be paid to the user out of the fee collector balance
+        assert_with_error!(e, trade.fee_amount >= 0, Error::ErrOrderFeeLimitExceeded);
+
+        // the buyer never pays more and the seller never gets less than the signed price,
+        // which holds for the settled amount too so its rounding always favors the user
+        let scale = 10_i128.pow(price_decimals);
+        let within_price = match (
+            trade.amount.checked_mul(scale),
+            trade.quantity.checked_mul(self.price),
+        ) {
+            (Some(scaled_amount), Some(limit_notional)) => match trade.side {
+                PurchaseSide::Buy => trade.price <= self.price && scaled_amount <= limit_notional,
+                PurchaseSide::Sell => trade.price >= self.price && scaled_amount >= limit_notional,
+            },
+            _ => false,
+        };
+        assert_with_error!(e, within_price, Error::ErrPriceLimitExceeded);
+
+        assert_with_error!(
+            e,
+            e.ledger().timestamp() <= self.expiry,
+            Error::ErrOrderExpired
+        );
+    }
+}
+
+impl TradeUploadUnit {
+    /// `amount` has to be `quantity * price` in quote token units,
+    /// only the rounding of the pair price decimals is tolerated.
+    fn assert_amount_matches_price(&self, e: &Env, price_decimals: u32) {
+        let scale = 10_i128.pow(price_decimals);
+        let matches_price = self.price > 0
+            && match (
+                self.quantity.checked_mul(self.price),
+                self.amount.checked_mul(scale),
+            ) {
+                (Some(notional), Some(scaled_amount)) => (scaled_amount - notional).abs() < scale,
+                _ => false,
+            };
+
+        assert_with_error!(e, matches_price, Error::ErrTradeAmountMismatch);
+    }
+}
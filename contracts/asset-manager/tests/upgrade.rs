@@ -0,0 +1,64 @@
+use soroban_sdk::{testutils::Address as AddressTestTrait, token, Address, Env};
+
+// the current build of the crate
+mod asset_manager_wasm {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/asset_manager.wasm"
+    );
+}
+
+// the crate built with the `next-version` feature, which bumps `CONTRACT_VERSION`
+mod next_asset_manager_wasm {
+    soroban_sdk::contractimport!(
+        file = "../../target/next-version/wasm32-unknown-unknown/release/asset_manager.wasm"
+    );
+}
+
+#[test]
+fn check_upgrade_keeps_storage() {
+    let e = Env::default();
+    e.mock_all_auths();
+    // instantiating the full contract WASM twice goes beyond the default test budget
+    e.budget().reset_unlimited();
+
+    let owner = Address::random(&e);
+    let operator = Address::random(&e);
+    let fee_collector = Address::random(&e);
+    let user = Address::random(&e);
+
+    let token = e.register_stellar_asset_contract(Address::random(&e));
+    token::StellarAssetClient::new(&e, &token).mint(&user, &10);
+
+    let contract_id = e.register_contract_wasm(None, asset_manager_wasm::WASM);
+    let client = asset_manager_wasm::Client::new(&e, &contract_id);
+    client.initialize(&owner, &operator, &fee_collector);
+    client.set_token_status(&token, &asset_manager_wasm::ListingStatus::Listed);
+    client.deposit(&user, &token, &10);
+
+    let current_version = client.version();
+
+    let current_wasm_hash = e.deployer().upload_contract_wasm(asset_manager_wasm::WASM);
+    let new_wasm_hash = e
+        .deployer()
+        .upload_contract_wasm(next_asset_manager_wasm::WASM);
+    assert_ne!(current_wasm_hash, new_wasm_hash);
+
+    client.upgrade(&new_wasm_hash);
+
+    assert_eq!(e.auths()[0].0, owner);
+
+    let client = next_asset_manager_wasm::Client::new(&e, &contract_id);
+
+    assert_eq!(client.owner(), owner);
+    assert_eq!(client.operator_manager(), operator);
+    assert_eq!(client.fee_collector(), fee_collector);
+    assert!(client.is_token_listed(&token));
+    assert_eq!(client.balances(&user, &token).balance, 10);
+    assert_eq!(client.version(), current_version);
+
+    client.migrate();
+
+    assert_eq!(client.version(), current_version + 1);
+    // the storage is migrated once per version
+    assert!(client.try_migrate().is_err());
+}